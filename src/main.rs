@@ -1,8 +1,26 @@
-use crate::types::alphabet::alphabet;
-use crate::types::cla::{Args, Mode};
+use crate::types::add::add;
+use crate::types::alphabet::{alphabet, pairs, spell};
+use crate::types::anki::anki;
+use crate::types::cla::{completions, Args, ArticlesArgs, Mode};
+use crate::types::config::{config, Config};
+use crate::types::data::DataDir;
+use crate::types::deck::decks;
 use crate::types::download::download;
+use crate::types::error::Result;
+use crate::types::exam::exam;
+use crate::types::gender::audit;
+use crate::types::lint::lint;
+use crate::types::listen::listen;
+use crate::types::mix::mix;
 use crate::types::nouns::{articles, plural};
 use crate::types::numbers::numbers;
+use crate::types::sentences::sentences;
+use crate::types::serve::serve;
+use crate::types::speak::speak;
+use crate::types::stats::stats;
+use crate::types::time::time;
+use crate::types::translation::translation;
+use crate::types::ui::new_ui;
 use crate::types::verbs::verbs;
 use crate::types::web::play_audio;
 
@@ -11,13 +29,57 @@ mod types;
 fn main() {
     let args = Args::from_args();
 
-    match args.mode {
-        Mode::Articles => articles(),
-        Mode::Plural => plural(),
-        Mode::Verbs => verbs(),
-        Mode::Numbers => numbers(),
-        Mode::Alphabet => alphabet(),
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<()> {
+    let settings = Config::load(args.config.as_deref())?.with_args(args);
+    settings.apply();
+
+    /* The config and completions modes also work when there is no data directory */
+    let data = || DataDir::locate(settings.data_dir.as_deref());
+    let ui = || new_ui(&settings, args.protocol);
+    let mode = args
+        .mode
+        .clone()
+        .unwrap_or(Mode::Articles(ArticlesArgs::default()));
+    match mode {
+        Mode::Articles(_) => articles(&data()?, &settings, ui()?.as_mut()),
+        Mode::Plural => plural(&data()?, &settings, ui()?.as_mut()),
+        Mode::Verbs(_) => verbs(&data()?, &settings, ui()?.as_mut()),
+        Mode::Sentences => sentences(&data()?, &settings, ui()?.as_mut()),
+        Mode::Translation => translation(&data()?, &settings, ui()?.as_mut()),
+        Mode::Listen => listen(&data()?, &settings, ui()?.as_mut()),
+        Mode::Numbers(_) => numbers(&data()?, &settings, ui()?.as_mut()),
+        Mode::Alphabet(options) if options.pairs => {
+            pairs(&data()?, &settings, options.count, ui()?.as_mut())
+        }
+        Mode::Alphabet(options) if options.spell => {
+            spell(&data()?, &settings, &options, ui()?.as_mut())
+        }
+        Mode::Alphabet(_) => alphabet(&data()?, &settings, ui()?.as_mut()),
+        Mode::Time(options) => time(&data()?, &settings, &options, ui()?.as_mut()),
+        Mode::Exam(options) => exam(&data()?, &settings, &options, ui()?.as_mut()),
+        Mode::Mix(options) => mix(&data()?, &settings, &options, ui()?.as_mut()),
+        Mode::Speak(options) => speak(
+            &data()?,
+            &settings,
+            options.input.as_deref(),
+            ui()?.as_mut(),
+        ),
+        Mode::Serve(options) => serve(&data()?, &settings, &options),
         Mode::Play => play_audio(),
-        Mode::Download => download(),
+        Mode::Download(options) => download(&data()?, &settings, &options),
+        Mode::Add(options) => add(&data()?, &options, args.level, &args.tag),
+        Mode::Anki { action } => anki(&data()?, &settings, &action),
+        Mode::Audit => audit(&data()?, &settings),
+        Mode::Lint(options) => lint(&data()?, options.fix),
+        Mode::Stats => stats(&data()?),
+        Mode::Decks { action } => decks(&data()?, &action),
+        Mode::Config { action } => config(&settings, &action),
+        Mode::Completions { shell } => completions(shell),
     }
 }
@@ -1,10 +1,34 @@
+pub(crate) mod add;
 pub(crate) mod alphabet;
+pub(crate) mod anki;
 pub(crate) mod audio;
+pub(crate) mod choice;
 pub(crate) mod cla;
+pub(crate) mod config;
+pub(crate) mod data;
+pub(crate) mod deck;
 pub(crate) mod download;
+pub(crate) mod error;
+pub(crate) mod exam;
+pub(crate) mod exercise;
+pub(crate) mod gender;
+pub(crate) mod lint;
+pub(crate) mod listen;
+pub(crate) mod mix;
 pub(crate) mod nouns;
 pub(crate) mod numbers;
-mod phrases;
+pub(crate) mod phrases;
+pub(crate) mod progress;
+pub(crate) mod protocol;
+pub(crate) mod sentences;
+pub(crate) mod serve;
+pub(crate) mod speak;
+pub(crate) mod stats;
+pub(crate) mod tags;
+pub(crate) mod time;
+pub(crate) mod translation;
+pub(crate) mod tui;
+pub(crate) mod ui;
 pub(crate) mod utils;
 pub(crate) mod verbs;
 pub(crate) mod web;
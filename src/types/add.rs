@@ -0,0 +1,474 @@
+use std::path::Path;
+
+use clap::{Args as ClapArgs, Subcommand, ValueEnum};
+use csv::StringRecord;
+
+use crate::types::data::DataDir;
+use crate::types::download::{download_noun, download_phrase, download_verb};
+use crate::types::error::{Error, Result};
+use crate::types::lint::{is_article, starts_with_uppercase};
+use crate::types::nouns::{Noun, NOUNS_FILE};
+use crate::types::phrases::{Phrase, Phrases};
+use crate::types::tags::Level;
+use crate::types::verbs::{Verb, VERBS_FILE};
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct AddArgs {
+    #[command(subcommand)]
+    pub(crate) entry: Entry,
+
+    /// Only add the entry, without fetching its audio files
+    #[clap(long, global = true)]
+    pub(crate) no_download: bool,
+}
+
+/// The new entry for the word lists.  Its level and tags are taken from --level and --tag.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum Entry {
+    /// Add a noun to nouns.csv
+    Noun(NounArgs),
+    /// Add a verb to verbs.csv
+    Verb(VerbArgs),
+    /// Add a phrase to phrases.csv
+    Phrase(PhraseArgs),
+}
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct NounArgs {
+    /// The article of the singular: der, die or das
+    #[clap(long)]
+    pub(crate) article: String,
+
+    /// The singular, such as Brücke
+    #[clap(long)]
+    pub(crate) singular: String,
+
+    /// The plural, such as Brücken (missing for nouns without one)
+    #[clap(long)]
+    pub(crate) plural: Option<String>,
+
+    /// The English translation, such as bridge
+    #[clap(long)]
+    pub(crate) english: String,
+}
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct VerbArgs {
+    /// The infinitive, without sich, such as anrufen
+    #[clap(long)]
+    pub(crate) german: String,
+
+    /// The English translation, such as "to call (on the phone)"
+    #[clap(long)]
+    pub(crate) english: String,
+
+    /// The Präsens for ich, du, er, wir, ihr and sie, without the separable prefix, such as
+    /// rufe,rufst,ruft,rufen,ruft,rufen
+    #[clap(long, value_delimiter = ',', required = true)]
+    pub(crate) present: Vec<String>,
+
+    /// The Präteritum for ich, du, er, wir, ihr and sie, such as rief,riefst,rief,riefen,rieft,riefen
+    #[clap(long, value_delimiter = ',')]
+    pub(crate) praeteritum: Vec<String>,
+
+    /// The auxiliary of the Perfekt: haben or sein
+    #[clap(long, requires = "partizip")]
+    pub(crate) auxiliary: Option<String>,
+
+    /// The Partizip II, such as angerufen
+    #[clap(long, requires = "auxiliary")]
+    pub(crate) partizip: Option<String>,
+
+    /// The Imperativ for du, ihr and Sie, such as "ruf,ruft,rufen Sie"
+    #[clap(long, value_delimiter = ',')]
+    pub(crate) imperativ: Vec<String>,
+
+    /// The separable prefix, such as an
+    #[clap(long)]
+    pub(crate) prefix: Option<String>,
+
+    /// The verb takes a reflexive pronoun (ich wasche mich)
+    #[clap(long)]
+    pub(crate) reflexive: bool,
+}
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct PhraseArgs {
+    /// The German phrase, such as "Wo ist die Brücke?"
+    #[clap(long)]
+    pub(crate) german: String,
+
+    /// The English translation, such as "Where is the bridge?"
+    #[clap(long)]
+    pub(crate) english: String,
+}
+
+/// Adds the entry to its word list, sorted like the other entries, and fetches the audio files of
+/// the new entry only
+pub(crate) fn add(
+    data: &DataDir,
+    options: &AddArgs,
+    level: Option<Level>,
+    tags: &[String],
+) -> Result<()> {
+    let tags = tags.join(" ");
+    let level = level.map(|level| level.to_string()).unwrap_or_default();
+
+    match &options.entry {
+        Entry::Noun(noun) => add_noun(data, noun, &tags, &level, options.no_download),
+        Entry::Verb(verb) => add_verb(data, verb, &tags, &level, options.no_download),
+        Entry::Phrase(phrase) => add_phrase(data, phrase, &tags, &level, options.no_download),
+    }
+}
+
+pub(crate) fn add_noun(
+    data: &DataDir,
+    args: &NounArgs,
+    tags: &str,
+    level: &str,
+    no_download: bool,
+) -> Result<()> {
+    check_noun(args)?;
+    if Noun::read(data)?
+        .iter()
+        .any(|noun| noun.singular == args.singular && noun.article == args.article)
+    {
+        return Err(Error::Entry(format!(
+            "{} {} is already in {}",
+            args.article, args.singular, NOUNS_FILE
+        )));
+    }
+
+    let plural = args.plural.clone().unwrap_or_default();
+    let line = insert_row(
+        &data.file(NOUNS_FILE),
+        "singular",
+        &[
+            ("english", args.english.as_str()),
+            ("article", &args.article),
+            ("singular", &args.singular),
+            ("plural", &plural),
+            ("tags", tags),
+            ("level", level),
+        ],
+    )?;
+    println!(
+        "Added {} {} to {} (line {})",
+        args.article, args.singular, NOUNS_FILE, line
+    );
+
+    if no_download {
+        return Ok(());
+    }
+    match Noun::read(data)?
+        .into_iter()
+        .find(|noun| noun.singular == args.singular && noun.article == args.article)
+    {
+        Some(noun) => download_noun(data, &noun),
+        None => Ok(()),
+    }
+}
+
+fn check_noun(args: &NounArgs) -> Result<()> {
+    let invalid = |message: String| Err(Error::Entry(message));
+
+    if !is_article(&args.article) {
+        return invalid(format!(
+            "unknown article '{}' (expected der, die or das)",
+            args.article
+        ));
+    }
+    for noun in std::iter::once(&args.singular).chain(args.plural.iter()) {
+        if !starts_with_uppercase(noun) {
+            return invalid(format!("'{}' is not capitalised", noun));
+        }
+    }
+    if args.english.trim().is_empty() {
+        return invalid("the English translation is empty".to_string());
+    }
+    Ok(())
+}
+
+fn add_verb(
+    data: &DataDir,
+    args: &VerbArgs,
+    tags: &str,
+    level: &str,
+    no_download: bool,
+) -> Result<()> {
+    check_verb(args)?;
+    if Verb::read(data)?
+        .iter()
+        .any(|verb| verb.german == args.german)
+    {
+        return Err(Error::Entry(format!(
+            "{} is already in {}",
+            args.german, VERBS_FILE
+        )));
+    }
+
+    let form = |forms: &[String], index: usize| forms.get(index).cloned().unwrap_or_default();
+    let present: Vec<String> = (0..6).map(|i| form(&args.present, i)).collect();
+    let praeteritum: Vec<String> = (0..6).map(|i| form(&args.praeteritum, i)).collect();
+    let imperativ: Vec<String> = (0..3).map(|i| form(&args.imperativ, i)).collect();
+    let auxiliary = args.auxiliary.clone().unwrap_or_default();
+    let partizip = args.partizip.clone().unwrap_or_default();
+    let prefix = args.prefix.clone().unwrap_or_default();
+    let reflexive = if args.reflexive { "true" } else { "" };
+
+    let line = insert_row(
+        &data.file(VERBS_FILE),
+        "german",
+        &[
+            ("english", args.english.as_str()),
+            ("german", &args.german),
+            ("ich", &present[0]),
+            ("du", &present[1]),
+            ("er", &present[2]),
+            ("wir", &present[3]),
+            ("ihr", &present[4]),
+            ("sie", &present[5]),
+            ("praeteritum_ich", &praeteritum[0]),
+            ("praeteritum_du", &praeteritum[1]),
+            ("praeteritum_er", &praeteritum[2]),
+            ("praeteritum_wir", &praeteritum[3]),
+            ("praeteritum_ihr", &praeteritum[4]),
+            ("praeteritum_sie", &praeteritum[5]),
+            ("auxiliary", &auxiliary),
+            ("partizip", &partizip),
+            ("imperativ_du", &imperativ[0]),
+            ("imperativ_ihr", &imperativ[1]),
+            ("imperativ_sie", &imperativ[2]),
+            ("prefix", &prefix),
+            ("reflexive", reflexive),
+            ("tags", tags),
+            ("level", level),
+        ],
+    )?;
+    println!("Added {} to {} (line {})", args.german, VERBS_FILE, line);
+
+    if no_download {
+        return Ok(());
+    }
+    match Verb::read(data)?
+        .into_iter()
+        .find(|verb| verb.german == args.german)
+    {
+        Some(verb) => download_verb(data, &verb),
+        None => Ok(()),
+    }
+}
+
+fn check_verb(args: &VerbArgs) -> Result<()> {
+    let invalid = |message: String| Err(Error::Entry(message));
+
+    if args.german.trim().is_empty() || args.english.trim().is_empty() {
+        return invalid("the infinitive and the English translation are needed".to_string());
+    }
+    if args.present.len() != 6 {
+        return invalid(format!(
+            "expected 6 forms of the Präsens (ich, du, er, wir, ihr, sie), found {}",
+            args.present.len()
+        ));
+    }
+    if !args.praeteritum.is_empty() && args.praeteritum.len() != 6 {
+        return invalid(format!(
+            "expected 6 forms of the Präteritum (ich, du, er, wir, ihr, sie), found {}",
+            args.praeteritum.len()
+        ));
+    }
+    if !args.imperativ.is_empty() && args.imperativ.len() != 3 {
+        return invalid(format!(
+            "expected 3 forms of the Imperativ (du, ihr, Sie), found {}",
+            args.imperativ.len()
+        ));
+    }
+    if let Some(auxiliary) = &args.auxiliary {
+        if auxiliary != "haben" && auxiliary != "sein" {
+            return invalid(format!(
+                "auxiliary '{}' is neither haben nor sein",
+                auxiliary
+            ));
+        }
+    }
+    if args
+        .present
+        .iter()
+        .chain(args.praeteritum.iter())
+        .chain(args.imperativ.iter())
+        .any(|form| form.trim().is_empty())
+    {
+        return invalid("some of the forms are empty".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn add_phrase(
+    data: &DataDir,
+    args: &PhraseArgs,
+    tags: &str,
+    level: &str,
+    no_download: bool,
+) -> Result<()> {
+    /* Phrase::new panics on phrases of the wrong length */
+    for phrase in [&args.german, &args.english] {
+        let length = phrase.chars().count();
+        if !(12..=64).contains(&length) {
+            return Err(Error::Entry(format!(
+                "'{}' has {} characters, but phrases have between 12 and 64",
+                phrase, length
+            )));
+        }
+    }
+
+    let mut phrases = Phrases::read(data)?;
+    if phrases.iter().any(|phrase| phrase.german == args.german) {
+        return Err(Error::Entry(format!(
+            "'{}' is already in the phrases",
+            args.german
+        )));
+    }
+
+    let mut phrase = Phrase::new(args.german.clone(), args.english.clone());
+    phrase.tags = Some(tags.to_string()).filter(|tags| !tags.is_empty());
+    phrase.level = Level::from_str(level, false).ok();
+    let audio = phrase.audio_file_path(data);
+
+    /* Written right away, so that a failure to save the phrase is reported */
+    phrases.append(&mut vec![phrase]);
+    phrases.write()?;
+    println!("Added '{}' to the phrases", args.german);
+
+    if no_download {
+        return Ok(());
+    }
+    match phrases.iter().find(|phrase| phrase.german == args.german) {
+        Some(phrase) if !audio.exists() => download_phrase(data, phrase),
+        _ => Ok(()),
+    }
+}
+
+/// Inserts the row into the CSV file before the first row that sorts after it by the given
+/// column, and returns its line number.  The optional columns (such as tags) that the row has but
+/// the file does not are added to the file.
+fn insert_row(file: &Path, column: &str, row: &[(&str, &str)]) -> Result<u64> {
+    let mut reader = csv::Reader::from_path(file).map_err(|e| Error::csv(file, &e))?;
+    let mut headers = reader.headers().map_err(|e| Error::csv(file, &e))?.clone();
+    let mut records = reader
+        .records()
+        .collect::<std::result::Result<Vec<StringRecord>, csv::Error>>()
+        .map_err(|e| Error::csv(file, &e))?;
+
+    for (name, value) in row {
+        if !value.is_empty() && !headers.iter().any(|header| header == *name) {
+            headers.push_field(name);
+            for record in records.iter_mut() {
+                record.push_field("");
+            }
+        }
+    }
+
+    let value = |name: &str| {
+        row.iter()
+            .find(|(column, _)| *column == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_default()
+    };
+    let record: StringRecord = headers.iter().map(value).collect();
+
+    let key = sort_key(value(column));
+    let position = headers.iter().position(|header| header == column);
+    let index = records
+        .iter()
+        .position(|record| {
+            position
+                .and_then(|position| record.get(position))
+                .is_some_and(|other| sort_key(other) > key)
+        })
+        .unwrap_or(records.len());
+    records.insert(index, record);
+
+    let mut writer = csv::Writer::from_path(file).map_err(|e| Error::csv(file, &e))?;
+    writer
+        .write_record(&headers)
+        .map_err(|e| Error::csv(file, &e))?;
+    for record in records.iter() {
+        writer
+            .write_record(record)
+            .map_err(|e| Error::csv(file, &e))?;
+    }
+    writer.flush().map_err(|e| Error::io(file, e))?;
+
+    /* The header is on line 1 */
+    Ok(index as u64 + 2)
+}
+
+/// The order of the nouns and verbs: ignoring the case, with the umlauts sorted as their vowels
+/// and ß as ss, so Äpfel comes right after Apfel
+fn sort_key(word: &str) -> String {
+    word.to_lowercase()
+        .replace('ä', "a")
+        .replace('ö', "o")
+        .replace('ü', "u")
+        .replace('ß', "ss")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::types::add::{check_noun, insert_row, sort_key, NounArgs};
+
+    #[test]
+    fn sorted_like_the_word_lists() {
+        assert!(sort_key("Äpfel") < sort_key("Arzt"));
+        assert_eq!(sort_key("Apfel"), sort_key("äpfel"));
+        assert_eq!(sort_key("Straße"), sort_key("strasse"));
+    }
+
+    #[test]
+    fn insert_sorted_row() {
+        let file = std::env::temp_dir().join("practice-deutsch-insert-row.csv");
+        fs::write(
+            &file,
+            "english,article,singular,plural\napple,der,Apfel,Äpfel\nfriend,der,Freund,Freunde\n",
+        )
+        .unwrap();
+
+        let line = insert_row(
+            &file,
+            "singular",
+            &[
+                ("english", "bridge"),
+                ("article", "die"),
+                ("singular", "Brücke"),
+                ("plural", "Brücken"),
+                ("level", "A2"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(line, 3);
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "english,article,singular,plural,level\n\
+             apple,der,Apfel,Äpfel,\n\
+             bridge,die,Brücke,Brücken,A2\n\
+             friend,der,Freund,Freunde,\n"
+        );
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn invalid_nouns() {
+        let noun = |article: &str, singular: &str| NounArgs {
+            article: article.to_string(),
+            singular: singular.to_string(),
+            plural: None,
+            english: "bridge".to_string(),
+        };
+        assert!(check_noun(&noun("die", "Brücke")).is_ok());
+        assert!(check_noun(&noun("dei", "Brücke")).is_err());
+        assert!(check_noun(&noun("die", "brücke")).is_err());
+    }
+}
@@ -1,5 +1,552 @@
-use crate::types::audio::pronounce;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
-pub(crate) fn alphabet() {
-    pronounce("audio/alphabet")
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::audio::{
+    play_file_or_print_error, play_file_slowly_or_print_error, playback_enabled, pronounce,
+};
+use crate::types::cla::AlphabetArgs;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::nouns::Noun;
+use crate::types::progress::History;
+use crate::types::ui::{Message, Ui};
+
+/// The letters that are commonly taken for each other when heard
+const CONFUSABLE: [(&str, &str); 7] = [
+    ("e", "i"),
+    ("a", "r"),
+    ("g", "j"),
+    ("v", "f"),
+    ("ä", "e"),
+    ("ü", "i"),
+    ("ß", "s"),
+];
+
+/// The DIN 5009 spelling alphabet, as in B wie Berta on the phone
+const SPELLING_ALPHABET: [(&str, &str); 30] = [
+    ("A", "Anton"),
+    ("Ä", "Ärger"),
+    ("B", "Berta"),
+    ("C", "Cäsar"),
+    ("D", "Dora"),
+    ("E", "Emil"),
+    ("F", "Friedrich"),
+    ("G", "Gustav"),
+    ("H", "Heinrich"),
+    ("I", "Ida"),
+    ("J", "Julius"),
+    ("K", "Kaufmann"),
+    ("L", "Ludwig"),
+    ("M", "Martha"),
+    ("N", "Nordpol"),
+    ("O", "Otto"),
+    ("Ö", "Ökonom"),
+    ("P", "Paula"),
+    ("Q", "Quelle"),
+    ("R", "Richard"),
+    ("S", "Samuel"),
+    ("ß", "Eszett"),
+    ("T", "Theodor"),
+    ("U", "Ulrich"),
+    ("Ü", "Übermut"),
+    ("V", "Viktor"),
+    ("W", "Wilhelm"),
+    ("X", "Xanthippe"),
+    ("Y", "Ypsilon"),
+    ("Z", "Zacharias"),
+];
+
+/// Common German first names and surnames, to spell as on the phone
+const NAMES: [&str; 30] = [
+    "Müller",
+    "Schmidt",
+    "Schneider",
+    "Fischer",
+    "Weber",
+    "Meyer",
+    "Wagner",
+    "Becker",
+    "Schulz",
+    "Hoffmann",
+    "Koch",
+    "Richter",
+    "Klein",
+    "Wolf",
+    "Schröder",
+    "Neumann",
+    "Schwarz",
+    "Zimmermann",
+    "Krüger",
+    "Hartmann",
+    "Lange",
+    "Jürgen",
+    "Günther",
+    "Jörg",
+    "Birgit",
+    "Heike",
+    "Sabine",
+    "Stefanie",
+    "Yvonne",
+    "Xaver",
+];
+
+/// The mode of the history rows with the letter heard and the one typed instead
+const CONFUSION: &str = "alphabet confusion";
+
+pub(crate) fn alphabet(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    pronounce(data, "alphabet", config, ui, |_| true)
+}
+
+/// Plays one letter of a pair that sounds alike, such as e and i, and asks which it was.  The
+/// pairs confused most often are asked most often.
+pub(crate) fn pairs(data: &DataDir, config: &Config, count: usize, ui: &mut dyn Ui) -> Result<()> {
+    if !playback_enabled() {
+        return ui.finish(vec!["This mode needs the audio, which is turned off".into()]);
+    }
+
+    let confusions = Confusions::read(&History::read(data)?);
+    let mut pairs: Vec<(String, String)> = CONFUSABLE
+        .iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect();
+    for (heard, typed, _) in confusions.most_frequent() {
+        if !pairs.iter().any(|pair| same_pair(pair, heard, typed)) {
+            pairs.push((heard.to_string(), typed.to_string()));
+        }
+    }
+    pairs.retain(|(a, b)| letter_file(data, a).is_file() && letter_file(data, b).is_file());
+    if pairs.is_empty() {
+        return ui.finish(vec![format!(
+            "No recorded letters found in {}",
+            data.audio("alphabet").display()
+        )
+        .into()]);
+    }
+
+    ui.start("letters that sound alike", count);
+
+    let mut rng = thread_rng();
+    let mut answered = 0;
+    while answered < count {
+        let Ok((a, b)) = pairs.choose_weighted(&mut rng, |(a, b)| 1 + confusions.between(a, b))
+        else {
+            break;
+        };
+        let (heard, other) = if rand::random() { (a, b) } else { (b, a) };
+        let exercise = Pair {
+            heard,
+            other,
+            file: letter_file(data, heard),
+        };
+        match attempt(&exercise, data, config, ui, count - answered)? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Correct | Outcome::Wrong | Outcome::Skipped => answered += 1,
+        }
+    }
+
+    /* Read again, with the mistakes of this session */
+    let confusions = Confusions::read(&History::read(data)?);
+    let mut summary = vec![Message::new("Finished the letters that sound alike")];
+    summary.extend(
+        confusions
+            .most_frequent()
+            .into_iter()
+            .take(5)
+            .map(|(heard, typed, times)| {
+                Message::new(format!(" - {} taken for {}: {} times", heard, typed, times))
+            }),
+    );
+    ui.finish(summary)
+}
+
+/// Plays a noun (or a name) letter by letter, or with the spelling alphabet, and asks for it
+pub(crate) fn spell(
+    data: &DataDir,
+    config: &Config,
+    options: &AlphabetArgs,
+    ui: &mut dyn Ui,
+) -> Result<()> {
+    if !playback_enabled() {
+        return ui.finish(vec!["This mode needs the audio, which is turned off".into()]);
+    }
+
+    /* Only the words whose letters are all recorded */
+    let nouns: Vec<Noun> = if options.names {
+        vec![]
+    } else {
+        Selection::new(data, config)?.nouns(data)?
+    };
+    let words: Vec<(&str, Option<&Noun>)> = if options.names {
+        NAMES.iter().map(|name| (*name, None)).collect()
+    } else {
+        nouns
+            .iter()
+            .map(|noun| (noun.singular.as_str(), Some(noun)))
+            .collect()
+    };
+    let mut spelled: Vec<Spelled> = words
+        .into_iter()
+        .filter_map(|(word, noun)| Spelled::new(data, word, noun, options.din))
+        .collect();
+    if spelled.is_empty() {
+        let missing = if options.din {
+            "No words found with all their letters recorded in the spelling alphabet, record them \
+             with: practice-deutsch download --provider manual"
+        } else {
+            "No words found with all their letters recorded"
+        };
+        return ui.finish(vec![missing.into()]);
+    }
+    spelled.shuffle(&mut thread_rng());
+    spelled.truncate(options.count);
+
+    let title = if options.names {
+        "spelled names"
+    } else {
+        "spelled nouns"
+    };
+    ui.start(title, spelled.len());
+
+    while !spelled.is_empty() {
+        let exercise = spelled.remove(0);
+        match attempt(&exercise, data, config, ui, spelled.len() + 1)? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Wrong => spelled.push(exercise),
+            Outcome::Correct | Outcome::Skipped => {}
+        }
+    }
+
+    ui.finish(vec![format!("Finished the {}", title).into()])
+}
+
+/// The text to record for each letter of the spelling alphabet, such as B wie Berta, and its file
+pub(crate) fn spelling_alphabet(data: &DataDir) -> Vec<(String, PathBuf)> {
+    SPELLING_ALPHABET
+        .iter()
+        .map(|(letter, word)| {
+            (
+                format!("{} wie {}", letter, word),
+                spelling_alphabet_file(data, &letter.to_lowercase()),
+            )
+        })
+        .collect()
+}
+
+/// Keeps the letter typed instead of the one heard, for the drill of the letters that sound alike
+pub(crate) fn record_confusion(data: &DataDir, heard: &str, typed: &str) {
+    let typed = typed.trim().to_lowercase();
+    /* Typos such as a whole word say nothing about how the letters sound */
+    if typed.chars().count() == 1 && typed != heard {
+        History::record(data, CONFUSION, heard, &typed, false);
+    }
+}
+
+/// Whether the history row is a confusion of two letters, which is not an answer of its own
+pub(crate) fn is_confusion(mode: &str) -> bool {
+    mode == CONFUSION
+}
+
+fn letter_file(data: &DataDir, letter: &str) -> PathBuf {
+    data.audio("alphabet").join(letter).with_extension("mp3")
+}
+
+fn spelling_alphabet_file(data: &DataDir, letter: &str) -> PathBuf {
+    data.audio("alphabet")
+        .join("din")
+        .join(letter)
+        .with_extension("mp3")
+}
+
+fn same_pair((a, b): &(String, String), c: &str, d: &str) -> bool {
+    (a == c && b == d) || (a == d && b == c)
+}
+
+/// How often each letter was taken for another, from the mistakes in the history
+struct Confusions(BTreeMap<(String, String), usize>);
+
+impl Confusions {
+    fn read(history: &History) -> Self {
+        let mut confusions = BTreeMap::new();
+        for attempt in history.attempts() {
+            if attempt.mode == CONFUSION {
+                *confusions
+                    .entry((attempt.item.clone(), attempt.variant.clone()))
+                    .or_default() += 1;
+            }
+        }
+        Confusions(confusions)
+    }
+
+    /// The times the letters were taken for each other, either way
+    fn between(&self, a: &str, b: &str) -> usize {
+        self.0
+            .iter()
+            .filter(|(pair, _)| same_pair(pair, a, b))
+            .map(|(_, times)| times)
+            .sum()
+    }
+
+    /// The letter heard, the one typed and how often, the most frequent first
+    fn most_frequent(&self) -> Vec<(&str, &str, usize)> {
+        let mut confusions: Vec<(&str, &str, usize)> = self
+            .0
+            .iter()
+            .map(|((heard, typed), times)| (heard.as_str(), typed.as_str(), *times))
+            .collect();
+        confusions.sort_by_key(|(_, _, times)| Reverse(*times));
+        confusions
+    }
+}
+
+/// One of two letters that sound alike
+struct Pair<'a> {
+    heard: &'a str,
+    other: &'a str,
+    file: PathBuf,
+}
+
+impl Exercise for Pair<'_> {
+    fn mode(&self) -> &'static str {
+        "alphabet"
+    }
+
+    fn item(&self) -> String {
+        self.heard.to_string()
+    }
+
+    fn variant(&self) -> String {
+        format!("or {}", self.other)
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        play_file_or_print_error(&self.file);
+        let (first, second) = if self.heard < self.other {
+            (self.heard, self.other)
+        } else {
+            (self.other, self.heard)
+        };
+        format!("Was it {} or {}", first, second).into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "one of the two letters"
+    }
+
+    fn check(&self, _config: &Config, answer: &str) -> Option<bool> {
+        let answer = answer.trim().to_lowercase();
+        if answer == self.heard {
+            Some(true)
+        } else if answer == self.other {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /* Both letters are played, one after the other, to hear the difference */
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        answer: &str,
+        correct: bool,
+    ) {
+        if correct {
+            ui.show(format!("Correct: {}, and this is {}", self.heard, self.other).into());
+        } else {
+            record_confusion(data, self.heard, answer);
+            ui.show(format!("Wrong! It was {}, and this is {}", self.heard, self.other).into());
+        }
+        play_file_or_print_error(&self.file);
+        play_file_or_print_error(&letter_file(data, self.other));
+    }
+
+    fn replay(&self, _data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.file);
+        } else {
+            play_file_or_print_error(&self.file);
+        }
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        None
+    }
+
+    fn solution(&self) -> String {
+        self.heard.to_string()
+    }
+
+    fn choices(&self) -> Vec<String> {
+        vec![self.heard.to_string(), self.other.to_string()]
+    }
+}
+
+/// A noun or a name read letter by letter
+struct Spelled<'a> {
+    word: &'a str,
+    /// The noun, to show and play its article after the answer (none for the names)
+    noun: Option<&'a Noun>,
+    letters: Vec<PathBuf>,
+    /// Whether the letters are read with the spelling alphabet, as in B wie Berta
+    din: bool,
+}
+
+impl<'a> Spelled<'a> {
+    /// The word with the recording of each of its letters, or none if one is missing
+    fn new(data: &DataDir, word: &'a str, noun: Option<&'a Noun>, din: bool) -> Option<Self> {
+        let letters = word
+            .to_lowercase()
+            .chars()
+            .map(|letter| {
+                let letter = letter.to_string();
+                let file = if din {
+                    spelling_alphabet_file(data, &letter)
+                } else {
+                    letter_file(data, &letter)
+                };
+                file.is_file().then_some(file)
+            })
+            .collect::<Option<Vec<PathBuf>>>()?;
+        Some(Spelled {
+            word,
+            noun,
+            letters,
+            din,
+        })
+    }
+
+    fn play(&self, slow: bool) {
+        for letter in self.letters.iter() {
+            if slow {
+                play_file_slowly_or_print_error(letter);
+            } else {
+                play_file_or_print_error(letter);
+            }
+        }
+    }
+}
+
+impl Exercise for Spelled<'_> {
+    fn mode(&self) -> &'static str {
+        "spelling"
+    }
+
+    fn item(&self) -> String {
+        self.word.to_string()
+    }
+
+    fn variant(&self) -> String {
+        if self.din { "din 5009" } else { "spelled" }.to_string()
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        self.play(false);
+        let what = if self.noun.is_some() { "noun" } else { "name" };
+        format!(
+            "Which {} was spelled ({} letters)",
+            what,
+            self.letters.len()
+        )
+        .into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the word that was spelled"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        Some(
+            config.spelling(&answer.trim().to_lowercase())
+                == config.spelling(&self.word.to_lowercase()),
+        )
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let verdict = if correct {
+            "Correct answer: "
+        } else {
+            "Wrong! Correct answer: "
+        };
+        match self.noun {
+            Some(noun) => {
+                ui.show(
+                    Message::new(verdict)
+                        .append(noun.with_article())
+                        .text(format!(" ({})", noun.english)),
+                );
+                noun.play_singular_with_article(data);
+            }
+            None => ui.show(Message::new(verdict).text(self.word)),
+        }
+    }
+
+    fn replay(&self, _data: &DataDir, slow: bool) -> bool {
+        self.play(slow);
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        self.noun.map(|noun| noun.english.clone())
+    }
+
+    fn solution(&self) -> String {
+        self.word.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::types::alphabet::{spelling_alphabet, Confusions};
+    use crate::types::data::DataDir;
+
+    #[test]
+    fn confusion_matrix() {
+        let mut counts = BTreeMap::new();
+        counts.insert(("e".to_string(), "i".to_string()), 3);
+        counts.insert(("i".to_string(), "e".to_string()), 1);
+        counts.insert(("g".to_string(), "j".to_string()), 2);
+        let confusions = Confusions(counts);
+
+        assert_eq!(confusions.between("e", "i"), 4);
+        assert_eq!(confusions.between("j", "g"), 2);
+        assert_eq!(confusions.between("v", "f"), 0);
+        assert_eq!(
+            confusions.most_frequent(),
+            vec![("e", "i", 3), ("g", "j", 2), ("i", "e", 1)]
+        );
+    }
+
+    #[test]
+    fn spelling_alphabet_letters() {
+        let spelling = spelling_alphabet(&DataDir::new(""));
+        for letter in ('a'..='z').chain(['ä', 'ö', 'ü', 'ß']) {
+            let file = format!("din/{}.mp3", letter);
+            assert!(
+                spelling.iter().any(|(_, path)| path.ends_with(&file)),
+                "{}",
+                letter
+            );
+        }
+        assert_eq!(spelling[2].0, "B wie Berta");
+        assert_eq!(spelling[21].0, "ß wie Eszett");
+    }
 }
@@ -0,0 +1,549 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Subcommand, ValueEnum};
+
+use crate::types::add::{add_noun, add_phrase, NounArgs, PhraseArgs};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::{Error, Result};
+use crate::types::lint::is_article;
+use crate::types::nouns::{Noun, NOUNS_FILE};
+use crate::types::phrases::Phrases;
+use crate::types::tags::{Level, Tagged};
+use crate::types::verbs::{Pronoun, Tense, Verb, VERBS_FILE};
+
+/// The file with the notes, in the format of Anki's text import
+const NOTES_FILE: &str = "practice-deutsch.txt";
+/// The directory with the audio files, to copy into Anki's collection.media directory
+const MEDIA_DIR: &str = "media";
+/// The parent deck in Anki, with one sub deck for the nouns, verbs and phrases each
+const DECK: &str = "Practice Deutsch";
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum AnkiAction {
+    /// Write the nouns, verbs and phrases with their audio files for Anki's text import
+    Export {
+        /// The directory to write the notes and the media directory to
+        #[clap(long, default_value = "anki")]
+        output: PathBuf,
+    },
+    /// Add the new nouns and phrases of Anki's text export (Notes in Plain Text) to the word lists
+    Import {
+        /// The exported file, such as "Practice Deutsch.txt"
+        file: PathBuf,
+
+        /// List the new entries and the changed translations without adding them
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+/// One Anki note, with the German on the front and the English on the back
+#[derive(Debug, PartialEq, Eq)]
+struct Note {
+    front: String,
+    back: String,
+    /// The Anki tags separated by spaces, such as "food A1"
+    tags: String,
+    /// The Anki deck, such as Practice Deutsch::Nouns (empty when the export has no deck column)
+    deck: String,
+}
+
+pub(crate) fn anki(data: &DataDir, config: &Config, action: &AnkiAction) -> Result<()> {
+    match action {
+        AnkiAction::Export { output } => export(data, config, output),
+        AnkiAction::Import { file, dry_run } => import(data, file, *dry_run),
+    }
+}
+
+/// Writes the selected nouns, verbs and phrases as notes for Anki's text import, with a copy of
+/// their audio files in the media directory
+fn export(data: &DataDir, config: &Config, output: &Path) -> Result<()> {
+    let selection = Selection::new(data, config)?;
+    let media = output.join(MEDIA_DIR);
+    fs::create_dir_all(&media).map_err(|e| Error::io(&media, e))?;
+
+    /* Anki keeps all the media in one directory, so the file names get the kind as a prefix */
+    let mut copied = 0;
+    let mut sound = |kind: &str, file: &Path| -> Result<String> {
+        let Some(name) = file.file_name() else {
+            return Ok(String::new());
+        };
+        if !file.is_file() {
+            return Ok(String::new());
+        }
+
+        let name = format!("practice-deutsch-{}-{}", kind, name.to_string_lossy());
+        let target = media.join(&name);
+        fs::copy(file, &target).map_err(|e| Error::io(&target, e))?;
+        copied += 1;
+        Ok(format!("[sound:{}]", name))
+    };
+
+    let mut notes = Vec::new();
+    for noun in selection.nouns(data)? {
+        let audio = sound("nouns", &noun.singular_with_article_file_path(data))?;
+        notes.push(Note {
+            front: format!("{}{}", noun_front(&noun), audio),
+            back: noun.english.clone(),
+            tags: anki_tags(&noun),
+            deck: format!("{}::Nouns", DECK),
+        });
+    }
+    for verb in selection.verbs(data)? {
+        let audio = sound("verbs", &verb.infinitive_audio_file_path(data))?;
+        let present = [
+            Pronoun::Ich,
+            Pronoun::Du,
+            Pronoun::Er,
+            Pronoun::Wir,
+            Pronoun::Ihr,
+            Pronoun::SiePlural,
+        ]
+        .iter()
+        .filter_map(|pronoun| verb.pronoun_conjugation(pronoun, &Tense::Praesens))
+        .collect::<Vec<String>>()
+        .join("<br>");
+        notes.push(Note {
+            front: format!("{}{}", verb.infinitive(), audio),
+            back: format!("{}<br><br>{}", verb.english, present),
+            tags: anki_tags(&verb),
+            deck: format!("{}::Verbs", DECK),
+        });
+    }
+    let phrases = Phrases::read(data)?;
+    for phrase in selection.phrases(&phrases) {
+        let audio = sound("phrases", &phrase.audio_file_path(data))?;
+        notes.push(Note {
+            front: format!("{}{}", phrase.german, audio),
+            back: phrase.english.clone(),
+            tags: anki_tags(phrase),
+            deck: format!("{}::Phrases", DECK),
+        });
+    }
+
+    let file = output.join(NOTES_FILE);
+    fs::write(&file, format_notes(&notes)).map_err(|e| Error::io(&file, e))?;
+
+    println!("------------------------------------------------------------");
+    println!("Exported {} notes to {}", notes.len(), file.display());
+    println!("Copied {} audio files to {}", copied, media.display());
+    println!("------------------------------------------------------------");
+    println!(
+        "Copy the audio files into Anki's collection.media directory and then import the notes"
+    );
+    println!("with File > Import");
+
+    Ok(())
+}
+
+/// The article (in the colour used by the articles mode), the singular and the plural, such as
+/// der Apfel, die Äpfel
+fn noun_front(noun: &Noun) -> String {
+    let colour = match noun.article.as_str() {
+        "der" => "blue",
+        "die" => "red",
+        _ => "green",
+    };
+    let mut front = format!(
+        "<span style=\"color: {}\">{}</span> {}",
+        colour, noun.article, noun.singular
+    );
+    if let Some(plural) = &noun.plural {
+        front.push_str(&format!(", die {}", plural));
+    }
+    front
+}
+
+/// The tags and the level, as Anki tags
+fn anki_tags(item: &impl Tagged) -> String {
+    let level = item.level().map(|level| level.to_string());
+    item.tags()
+        .into_iter()
+        .map(String::from)
+        .chain(level)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// The notes with the header lines that tell Anki how to import them
+fn format_notes(notes: &[Note]) -> String {
+    let field = |text: &str| text.replace(['\t', '\n'], " ");
+
+    let mut content = String::from(
+        "#separator:tab\n#html:true\n#notetype:Basic\n#tags column:3\n#deck column:4\n",
+    );
+    for note in notes {
+        content.push_str(&format!(
+            "{}\t{}\t{}\t{}\n",
+            field(&note.front),
+            field(&note.back),
+            field(&note.tags),
+            field(&note.deck)
+        ));
+    }
+    content
+}
+
+/// Adds the nouns and phrases of an Anki export that are not in the word lists yet, and reports
+/// the entries whose translation differs.  New verbs are only reported, as the export does not
+/// have their conjugation.
+fn import(data: &DataDir, file: &Path, dry_run: bool) -> Result<()> {
+    let content = fs::read_to_string(file).map_err(|e| Error::io(file, e))?;
+    let notes = parse_notes(&content).map_err(|e| Error::csv(file, &e))?;
+
+    let nouns = Noun::read(data)?;
+    let verbs = Verb::read(data)?;
+    let mut phrases: Vec<(String, String)> = Phrases::read(data)?
+        .iter()
+        .map(|phrase| (phrase.german.clone(), phrase.english.clone()))
+        .collect();
+
+    let (mut added, mut different, mut skipped) = (0, 0, 0);
+    let mut report_translation = |german: &str, here: &str, anki: &str, file: &str| {
+        if here != anki {
+            println!(
+                "Different translation of {}: '{}' in Anki, '{}' in {}",
+                german, anki, here, file
+            );
+            different += 1;
+        }
+    };
+
+    for note in notes.iter() {
+        let front = plain_text(&note.front);
+        let english = plain_text(note.back.split("<br").next().unwrap_or_default());
+        let (level, tags) = split_tags(&note.tags);
+
+        let result = match note_kind(note, &front) {
+            Kind::Noun => {
+                let Some(noun) = parse_noun(&front, &english) else {
+                    println!("Skipped {}: not a noun with its article", front);
+                    skipped += 1;
+                    continue;
+                };
+                match nouns
+                    .iter()
+                    .find(|n| n.singular == noun.singular && n.article == noun.article)
+                {
+                    Some(existing) => {
+                        report_translation(&front, &existing.english, &english, NOUNS_FILE);
+                        continue;
+                    }
+                    None if dry_run => {
+                        println!("New noun {}", front);
+                        Ok(())
+                    }
+                    None => add_noun(data, &noun, &tags, &level, true),
+                }
+            }
+            Kind::Verb => {
+                let german = front.strip_prefix("sich ").unwrap_or(&front);
+                match verbs.iter().find(|verb| verb.german == german) {
+                    Some(existing) => {
+                        report_translation(&front, &existing.english, &english, VERBS_FILE);
+                    }
+                    None => {
+                        println!(
+                            "New verb {} ({}): add it with its conjugation using add verb",
+                            front, english
+                        );
+                        skipped += 1;
+                    }
+                }
+                continue;
+            }
+            Kind::Phrase => match phrases.iter().find(|(german, _)| *german == front) {
+                Some((_, existing)) => {
+                    report_translation(&front, existing, &english, "the phrases");
+                    continue;
+                }
+                None if dry_run => {
+                    println!("New phrase {}", front);
+                    Ok(())
+                }
+                None => {
+                    let phrase = PhraseArgs {
+                        german: front.clone(),
+                        english: english.clone(),
+                    };
+                    add_phrase(data, &phrase, &tags, &level, true)
+                }
+            }
+            .inspect(|_| phrases.push((front.clone(), english.clone()))),
+        };
+
+        match result {
+            Ok(()) => added += 1,
+            Err(e) => {
+                println!("Skipped {}: {}", front, e);
+                skipped += 1;
+            }
+        }
+    }
+
+    println!("------------------------------------------------------------");
+    println!(
+        "{} new, {} with a different translation, {} skipped",
+        added, different, skipped
+    );
+    println!("------------------------------------------------------------");
+    if added > 0 && !dry_run {
+        println!("Run the download mode to fetch the audio files of the new entries");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Kind {
+    Noun,
+    Verb,
+    Phrase,
+}
+
+/// The kind of note, from its sub deck when the export has the deck column, or else from its
+/// front: nouns start with their article, and phrases have several words or end with punctuation
+fn note_kind(note: &Note, front: &str) -> Kind {
+    for (suffix, kind) in [
+        ("::Nouns", Kind::Noun),
+        ("::Verbs", Kind::Verb),
+        ("::Phrases", Kind::Phrase),
+    ] {
+        if note.deck.ends_with(suffix) {
+            return kind;
+        }
+    }
+
+    let words: Vec<&str> = front.split_whitespace().collect();
+    if words.len() > 1 && is_article(words[0]) && words.len() <= 4 {
+        Kind::Noun
+    } else if words.len() > 2 || front.ends_with(['.', '?', '!']) {
+        Kind::Phrase
+    } else {
+        Kind::Verb
+    }
+}
+
+/// The noun of a front such as "der Apfel, die Äpfel"
+fn parse_noun(front: &str, english: &str) -> Option<NounArgs> {
+    let (singular, plural) = match front.split_once(',') {
+        Some((singular, plural)) => (singular, Some(plural.trim())),
+        None => (front, None),
+    };
+    let (article, singular) = singular.trim().split_once(' ')?;
+    let plural = match plural {
+        Some(plural) => Some(plural.strip_prefix("die ")?.trim().to_string()),
+        None => None,
+    };
+
+    Some(NounArgs {
+        article: article.to_string(),
+        singular: singular.trim().to_string(),
+        plural,
+        english: english.to_string(),
+    })
+}
+
+/// The level (one of the Anki tags, such as A1) and the other tags
+fn split_tags(tags: &str) -> (String, String) {
+    let (levels, tags): (Vec<&str>, Vec<&str>) = tags
+        .split_whitespace()
+        .partition(|tag| Level::from_str(tag, false).is_ok());
+    (
+        levels
+            .first()
+            .map(|level| level.to_string())
+            .unwrap_or_default(),
+        tags.join(" "),
+    )
+}
+
+/// The text of an Anki field, without the HTML tags and the sound references
+fn plain_text(field: &str) -> String {
+    let mut text = String::new();
+    let mut rest = field;
+    while let Some(start) = rest.find(['<', '[']) {
+        text.push_str(&rest[..start]);
+        let end = if rest[start..].starts_with('<') {
+            rest[start..].find('>')
+        } else if rest[start..].starts_with("[sound:") {
+            rest[start..].find(']')
+        } else {
+            Some(0)
+        };
+        match end {
+            Some(0) => {
+                text.push('[');
+                rest = &rest[start + 1..];
+            }
+            Some(end) => rest = &rest[start + end + 1..],
+            None => rest = "",
+        }
+    }
+    text.push_str(rest);
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// The notes of Anki's text export.  The header lines (such as #separator:tab) give the separator
+/// and the columns with the tags, the deck, the note type and the GUID, which are not note fields.
+fn parse_notes(content: &str) -> std::result::Result<Vec<Note>, csv::Error> {
+    let mut separator = b'\t';
+    let mut tags_column = None;
+    let mut deck_column = None;
+    let mut other_columns = Vec::new();
+
+    let mut body = String::new();
+    for line in content.lines() {
+        let Some(header) = line.strip_prefix('#') else {
+            body.push_str(line);
+            body.push('\n');
+            continue;
+        };
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let column = value.trim().parse::<usize>().ok();
+        match name.trim() {
+            "separator" => {
+                separator = match value.trim() {
+                    "tab" | "Tab" => b'\t',
+                    "comma" | "Comma" => b',',
+                    "semicolon" | "Semicolon" => b';',
+                    "space" | "Space" => b' ',
+                    "pipe" | "Pipe" => b'|',
+                    "colon" | "Colon" => b':',
+                    other => other.bytes().next().unwrap_or(b'\t'),
+                }
+            }
+            "tags column" => tags_column = column,
+            "deck column" => deck_column = column,
+            "notetype column" | "guid column" => other_columns.extend(column),
+            _ => {}
+        }
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(separator)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(body.as_bytes());
+
+    let mut notes = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        /* The columns in the header lines start at 1 */
+        let column = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column - 1))
+                .unwrap_or_default()
+                .to_string()
+        };
+        let fields: Vec<&str> = record
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                let column = Some(index + 1);
+                column != tags_column
+                    && column != deck_column
+                    && !other_columns.contains(&(index + 1))
+            })
+            .map(|(_, field)| field)
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        notes.push(Note {
+            front: fields[0].to_string(),
+            back: fields.get(1).copied().unwrap_or_default().to_string(),
+            tags: column(tags_column),
+            deck: column(deck_column),
+        });
+    }
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::anki::{
+        format_notes, note_kind, noun_front, parse_notes, parse_noun, plain_text, split_tags, Kind,
+        Note,
+    };
+    use crate::types::nouns::Noun;
+
+    #[test]
+    fn round_trip_notes() {
+        let notes = vec![Note {
+            front: "<span style=\"color: blue\">der</span> Apfel, die Äpfel[sound:apfel.mp3]"
+                .to_string(),
+            back: "apple".to_string(),
+            tags: "food A1".to_string(),
+            deck: "Practice Deutsch::Nouns".to_string(),
+        }];
+        assert_eq!(parse_notes(&format_notes(&notes)).unwrap(), notes);
+    }
+
+    #[test]
+    fn parse_anki_export() {
+        let content = "#separator:tab\n#html:true\n#guid column:1\n#notetype column:2\n\
+                       #deck column:3\n#tags column:6\n\
+                       abc\tBasic\tDefault\tdie Brücke, die Brücken\tbridge\tcity A2\n";
+        let notes = parse_notes(content).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].front, "die Brücke, die Brücken");
+        assert_eq!(notes[0].back, "bridge");
+        assert_eq!(notes[0].deck, "Default");
+        assert_eq!(
+            split_tags(&notes[0].tags),
+            ("A2".to_string(), "city".to_string())
+        );
+        assert_eq!(note_kind(&notes[0], &notes[0].front), Kind::Noun);
+    }
+
+    #[test]
+    fn noun_from_front() {
+        let noun = Noun {
+            english: "apple".to_string(),
+            article: "der".to_string(),
+            singular: "Apfel".to_string(),
+            plural: Some("Äpfel".to_string()),
+            tags: None,
+            level: None,
+        };
+        let front = plain_text(&format!("{}[sound:apfel.mp3]", noun_front(&noun)));
+        assert_eq!(front, "der Apfel, die Äpfel");
+
+        let parsed = parse_noun(&front, "apple").unwrap();
+        assert_eq!(parsed.article, "der");
+        assert_eq!(parsed.singular, "Apfel");
+        assert_eq!(parsed.plural.as_deref(), Some("Äpfel"));
+    }
+
+    #[test]
+    fn kind_from_front() {
+        let note = |front: &str| Note {
+            front: front.to_string(),
+            back: String::new(),
+            tags: String::new(),
+            deck: "Default".to_string(),
+        };
+        for (front, kind) in [
+            ("das Brot", Kind::Noun),
+            ("sich waschen", Kind::Verb),
+            ("wohnen", Kind::Verb),
+            ("Wo ist die Brücke?", Kind::Phrase),
+        ] {
+            assert_eq!(note_kind(&note(front), front), kind);
+        }
+    }
+}
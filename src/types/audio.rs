@@ -1,94 +1,410 @@
-use std::error::Error;
+use std::cell::RefCell;
 use std::fs;
 use std::fs::File;
-use std::io::{stdin, BufReader};
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use rand::Rng;
-use rodio::{Decoder, OutputStream, Sink};
+use rodio::buffer::SamplesBuffer;
+use rodio::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rodio::cpal::{self, SampleFormat};
+use rodio::{Decoder, OutputStream, Sink, Source};
 
-pub(crate) fn pronounce(directory: &str) {
+use crate::types::alphabet::record_confusion;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::{Error, Result};
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::ui::{Message, Ui};
+
+/// How the audio files are played, which is the same for the whole program
+struct Playback {
+    enabled: bool,
+    speed: f32,
+}
+
+static PLAYBACK: OnceLock<Playback> = OnceLock::new();
+
+thread_local! {
+    /// The audio files played on this thread, with their speed, when they are passed on (such as
+    /// to the browser) instead of played
+    static FORWARDED: RefCell<Option<Vec<(PathBuf, f32)>>> = const { RefCell::new(None) };
+}
+
+/* How much slower the audio is replayed when asked */
+const SLOW: f32 = 0.7;
+
+/// Sets how the audio files are played.  Only the first call has an effect.
+pub(crate) fn configure_playback(enabled: bool, speed: f32) {
+    let _ = PLAYBACK.set(Playback { enabled, speed });
+}
+
+/// Passes the audio files played on this thread on, to be taken with `take_forwarded`, instead of
+/// playing them
+pub(crate) fn forward_playback() {
+    FORWARDED.with(|forwarded| *forwarded.borrow_mut() = Some(vec![]));
+}
+
+/// The audio files played on this thread since the last call, with their speed
+pub(crate) fn take_forwarded() -> Vec<(PathBuf, f32)> {
+    FORWARDED.with(|forwarded| {
+        forwarded
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    })
+}
+
+/// Whether the audio files are played (or passed on), that is, unless --no-audio was given
+pub(crate) fn playback_enabled() -> bool {
+    playback().enabled
+}
+
+fn playback() -> &'static Playback {
+    PLAYBACK.get_or_init(|| Playback {
+        enabled: true,
+        speed: 1.0,
+    })
+}
+
+/// Plays the audio files of the mode (such as numbers) at random, and asks for what was said.
+/// The file name is the expected answer, and only the files whose name is accepted by `include`
+/// are played.
+pub(crate) fn pronounce(
+    data: &DataDir,
+    mode: &'static str,
+    config: &Config,
+    ui: &mut dyn Ui,
+    include: impl Fn(&str) -> bool,
+) -> Result<()> {
+    if !playback().enabled {
+        return ui.finish(vec!["This mode needs the audio, which is turned off".into()]);
+    }
+
+    let directory = data.audio(mode);
     let mut files = Vec::new();
 
     loop {
         if files.is_empty() {
-            files = list_audio_files_in_directory(directory);
+            files = list_audio_files_in_directory(&directory)?;
+            files.retain(|file| {
+                file.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(&include)
+            });
             if files.is_empty() {
-                println!("No audio files found in {}", directory);
-                return;
+                return ui.finish(vec![format!(
+                    "No audio files found in {}",
+                    directory.display()
+                )
+                .into()]);
             }
 
-            println!("Loaded {} audio files from {}", files.len(), directory);
+            ui.start(
+                &format!("audio files from {}", directory.display()),
+                files.len(),
+            );
         }
 
         let mut rng = rand::thread_rng();
         let index = rng.gen_range(0..files.len());
-        let file = files.remove(index as usize);
-        if !play_file_and_verify(&file) {
-            return;
+        let file = files.remove(index);
+        let Some(exercise) = Listening::new(mode, &file) else {
+            ui.show(format!("Skipping audio file with an unreadable name: {:?}", file).into());
+            continue;
+        };
+        if attempt(&exercise, data, config, ui, files.len() + 1)? == Outcome::Quit {
+            return Ok(());
         }
     }
 }
 
-fn list_audio_files_in_directory(directory: &str) -> Vec<PathBuf> {
+pub(crate) fn list_audio_files_in_directory(directory: &Path) -> Result<Vec<PathBuf>> {
     fs::read_dir(directory)
-        .expect(&format!("Failed to read {}", directory))
-        .map(|r| r.unwrap().path())
+        .map_err(|e| Error::io(directory, e))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|e| Error::io(directory, e))
+        })
         .collect()
 }
 
-fn play_file_and_verify(file: &PathBuf) -> bool {
-    if let Err(e) = play_file(file) {
-        println!("Failed to play audio file: {:?} ({})", file, e);
-        return true;
-    }
-
-    loop {
-        let mut input = String::new();
-        stdin()
-            .read_line(&mut input)
-            .expect("Failed to read the user input");
-        let input = input.trim();
-
-        match input {
-            "quit" | "exit" => return false,
-            "" | "repeat" => {
-                play_file_or_print_error(file);
-                continue;
-            }
-            input => {
-                let expected = file.file_stem().unwrap().to_str().unwrap();
-                if !expected.eq(input) {
-                    println!("Wrong! It was: {}", expected);
-                    play_file_or_print_error(file);
-                }
-                return true;
-            }
-        }
+pub(crate) fn play_file_or_print_error(file: &Path) {
+    if !playback().enabled {
+        return;
     }
-}
 
-pub(crate) fn play_file_or_print_error(file: &Path) {
     if file.exists() {
         if let Err(e) = play_file(file) {
-            println!("Failed to play audio file: {:?} ({})", file, e);
+            eprintln!("{}", e);
         }
     } else {
-        println!("File not found: {:?}", file);
+        eprintln!("File not found: {:?}", file);
+    }
+}
+
+/// Plays the file slower than usual, to hear each sound
+pub(crate) fn play_file_slowly_or_print_error(file: &Path) {
+    if !playback().enabled {
+        return;
+    }
+
+    if let Err(e) = play_file_at(file, playback().speed * SLOW) {
+        eprintln!("{}", e);
     }
 }
 
-pub(crate) fn play_file(path: &Path) -> Result<(), Box<dyn Error>> {
+pub(crate) fn play_file(path: &Path) -> Result<()> {
+    play_file_at(path, playback().speed)
+}
+
+fn play_file_at(path: &Path, speed: f32) -> Result<()> {
+    let forwarded = FORWARDED.with(|forwarded| match forwarded.borrow_mut().as_mut() {
+        Some(files) => {
+            files.push((path.to_path_buf(), speed));
+            true
+        }
+        None => false,
+    });
+    if forwarded {
+        return Ok(());
+    }
+
     /* Based on: https://docs.rs/rodio/latest/rodio/ */
 
-    let (_stream, stream_handle) = OutputStream::try_default()?;
-    let file = File::open(path)?;
+    let (_stream, stream_handle) =
+        OutputStream::try_default().map_err(|e| Error::audio(path, e))?;
+    let file = File::open(path).map_err(|e| Error::io(path, e))?;
     let reader = BufReader::new(file);
-    let source = Decoder::new(reader)?;
-    let sink = Sink::try_new(&stream_handle).unwrap();
+    let source = Decoder::new(reader).map_err(|e| Error::audio(path, e))?;
+    let sink = Sink::try_new(&stream_handle).map_err(|e| Error::audio(path, e))?;
+    sink.set_speed(speed);
     sink.append(source);
     /* The sound plays in a separate audio thread, so we need to keep the main thread alive while it's playing. */
     sink.sleep_until_end();
 
     Ok(())
 }
+
+/// What was said in an audio file, whose name (such as 12.mp3) is the answer
+pub(crate) struct Listening {
+    mode: &'static str,
+    file: PathBuf,
+    answer: String,
+}
+
+impl Listening {
+    /// The file of the mode, such as numbers, or none if its name is not readable
+    pub(crate) fn new(mode: &'static str, file: &Path) -> Option<Self> {
+        Some(Listening {
+            mode,
+            file: file.to_path_buf(),
+            answer: file.file_stem()?.to_str()?.to_string(),
+        })
+    }
+}
+
+impl Exercise for Listening {
+    fn mode(&self) -> &'static str {
+        self.mode
+    }
+
+    fn item(&self) -> String {
+        self.answer.clone()
+    }
+
+    fn variant(&self) -> String {
+        "heard".to_string()
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        play_file_or_print_error(&self.file);
+        "What did you hear".into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "what was said"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        Some(config.spelling(&self.answer) == config.spelling(answer))
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        answer: &str,
+        correct: bool,
+    ) {
+        if !correct {
+            if self.mode == "alphabet" {
+                record_confusion(data, &self.answer, answer);
+            }
+            ui.show(format!("Wrong! It was: {}", self.answer).into());
+            play_file_or_print_error(&self.file);
+        }
+    }
+
+    fn replay(&self, _data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.file);
+        } else {
+            play_file_or_print_error(&self.file);
+        }
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        None
+    }
+
+    fn solution(&self) -> String {
+        self.answer.clone()
+    }
+}
+
+/// Audio recorded with the microphone, or read from a WAV file, to play back
+#[derive(Debug)]
+pub(crate) struct Recording {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl Recording {
+    /// Records the default microphone until `stop` returns, such as when enter is pressed
+    pub(crate) fn record(stop: impl FnOnce() -> Result<()>) -> Result<Self> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or_else(|| Error::recording("no microphone found"))?;
+        let config = device.default_input_config().map_err(Error::recording)?;
+
+        /* The samples arrive on the audio thread */
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let buffer = Arc::clone(&samples);
+        let on_error = |e| eprintln!("Recording failed: {}", e);
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => device.build_input_stream(
+                &config.config(),
+                move |data: &[f32], _: &_| buffer.lock().unwrap().extend_from_slice(data),
+                on_error,
+                None,
+            ),
+            SampleFormat::I16 => device.build_input_stream(
+                &config.config(),
+                move |data: &[i16], _: &_| {
+                    buffer.lock().unwrap().extend(
+                        data.iter()
+                            .map(|sample| cpal::Sample::to_sample::<f32>(*sample)),
+                    )
+                },
+                on_error,
+                None,
+            ),
+            SampleFormat::U16 => device.build_input_stream(
+                &config.config(),
+                move |data: &[u16], _: &_| {
+                    buffer.lock().unwrap().extend(
+                        data.iter()
+                            .map(|sample| cpal::Sample::to_sample::<f32>(*sample)),
+                    )
+                },
+                on_error,
+                None,
+            ),
+            format => {
+                return Err(Error::recording(format!(
+                    "unsupported sample format {}",
+                    format
+                )))
+            }
+        }
+        .map_err(Error::recording)?;
+
+        stream.play().map_err(Error::recording)?;
+        stop()?;
+        drop(stream);
+
+        let samples = std::mem::take(&mut *samples.lock().unwrap());
+        Ok(Recording {
+            channels: config.channels(),
+            sample_rate: config.sample_rate().0,
+            samples,
+        })
+    }
+
+    /// Reads a WAV file instead of recording, such as when there is no microphone
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| Error::io(path, e))?;
+        let source = Decoder::new(BufReader::new(file)).map_err(|e| Error::audio(path, e))?;
+        Ok(Recording {
+            channels: source.channels(),
+            sample_rate: source.sample_rate(),
+            samples: source.convert_samples::<f32>().collect(),
+        })
+    }
+
+    /// The length in seconds
+    pub(crate) fn duration(&self) -> f32 {
+        self.samples.len() as f32 / (self.channels as f32 * self.sample_rate as f32)
+    }
+
+    /// Plays the recording at its normal speed (unless the audio is turned off)
+    pub(crate) fn play(&self) -> Result<()> {
+        if !playback().enabled || self.samples.is_empty() {
+            return Ok(());
+        }
+
+        let (_stream, stream_handle) = OutputStream::try_default().map_err(Error::recording)?;
+        let sink = Sink::try_new(&stream_handle).map_err(Error::recording)?;
+        sink.append(SamplesBuffer::new(
+            self.channels,
+            self.sample_rate,
+            self.samples.clone(),
+        ));
+        sink.sleep_until_end();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::types::audio::Recording;
+
+    #[test]
+    fn read_wav_file() {
+        /* Half a second of silence: 16 bit mono at 8000 Hz */
+        let samples = 4000u32;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + samples * 2).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(samples * 2).to_le_bytes());
+        wav.resize(wav.len() + samples as usize * 2, 0);
+
+        let file = std::env::temp_dir().join("practice-deutsch-recording.wav");
+        fs::write(&file, wav).unwrap();
+        let recording = Recording::read(&file).unwrap();
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(recording.channels, 1);
+        assert_eq!(recording.sample_rate, 8000);
+        assert!((recording.duration() - 0.5).abs() < 0.01);
+    }
+}
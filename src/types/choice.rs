@@ -0,0 +1,152 @@
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::exercise::Exercise;
+use crate::types::ui::{Message, Ui};
+
+/* Enough wrong answers to make guessing unlikely, and few enough to take in at a glance */
+const MAX_DISTRACTORS: usize = 4;
+
+/// An exercise answered by the number of one of the answers shown, instead of typing the answer,
+/// for the beginners
+pub(crate) struct MultipleChoice<'a> {
+    exercise: &'a dyn Exercise,
+    choices: Vec<String>,
+}
+
+impl<'a> MultipleChoice<'a> {
+    /// The correct answer among up to four wrong ones, in random order, or none if the exercise
+    /// has no wrong answers to offer
+    pub(crate) fn new(exercise: &'a dyn Exercise, config: &Config) -> Option<Self> {
+        let mut rng = thread_rng();
+        let mut choices = exercise.choices();
+        if choices.is_empty() {
+            return None;
+        }
+
+        /* The wrong answers must not be spelled like the correct one, or like each other */
+        let correct = choices.remove(0);
+        let mut distractors: Vec<String> = Vec::new();
+        for choice in choices {
+            let spelling = config.spelling(&choice.to_lowercase());
+            if spelling != config.spelling(&correct.to_lowercase())
+                && !distractors
+                    .iter()
+                    .any(|distractor| config.spelling(&distractor.to_lowercase()) == spelling)
+            {
+                distractors.push(choice);
+            }
+        }
+        if distractors.is_empty() {
+            return None;
+        }
+
+        distractors.shuffle(&mut rng);
+        distractors.truncate(MAX_DISTRACTORS);
+        distractors.push(correct);
+        distractors.shuffle(&mut rng);
+        Some(MultipleChoice {
+            exercise,
+            choices: distractors,
+        })
+    }
+
+    /// The answer picked with its number, counting from 1
+    fn choice(&self, answer: &str) -> Option<&str> {
+        let number = answer.trim().parse::<usize>().ok()?;
+        self.choices.get(number.checked_sub(1)?).map(String::as_str)
+    }
+}
+
+impl Exercise for MultipleChoice<'_> {
+    fn mode(&self) -> &'static str {
+        self.exercise.mode()
+    }
+
+    fn item(&self) -> String {
+        self.exercise.item()
+    }
+
+    fn variant(&self) -> String {
+        self.exercise.variant()
+    }
+
+    fn present(&self, data: &DataDir) -> Message {
+        let choices = self
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| format!("{}) {}", index + 1, choice))
+            .collect::<Vec<String>>()
+            .join("  ");
+        self.exercise.present(data).text(format!("  {}", choices))
+    }
+
+    fn expected(&self) -> &'static str {
+        "the number of the answer"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        self.exercise.check(config, self.choice(answer)?)
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        config: &Config,
+        ui: &mut dyn Ui,
+        answer: &str,
+        correct: bool,
+    ) {
+        let answer = self.choice(answer).unwrap_or(answer);
+        self.exercise.feedback(data, config, ui, answer, correct);
+    }
+
+    fn replay(&self, data: &DataDir, slow: bool) -> bool {
+        self.exercise.replay(data, slow)
+    }
+
+    fn english(&self) -> Option<String> {
+        self.exercise.english()
+    }
+
+    fn solution(&self) -> String {
+        self.exercise.solution()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::choice::MultipleChoice;
+    use crate::types::config::Config;
+    use crate::types::exercise::Exercise;
+    use crate::types::nouns::{Noun, PluralExercise};
+
+    #[test]
+    fn pick_by_number() {
+        let config = Config::default();
+        let apfel = Noun::example("apple", "der", "Apfel", "Äpfel");
+        let exercise = PluralExercise(&apfel);
+
+        let choice = MultipleChoice::new(&exercise, &config).unwrap();
+        assert!((2..=5).contains(&choice.choices.len()));
+        assert_eq!(
+            choice
+                .choices
+                .iter()
+                .filter(|choice| *choice == "Äpfel")
+                .count(),
+            1
+        );
+
+        let number = choice.choices.iter().position(|c| c == "Äpfel").unwrap() + 1;
+        assert_eq!(choice.check(&config, &number.to_string()), Some(true));
+        let wrong = if number == 1 { 2 } else { 1 };
+        assert_eq!(choice.check(&config, &wrong.to_string()), Some(false));
+        assert_eq!(choice.check(&config, "Äpfel"), None);
+        assert_eq!(choice.check(&config, "0"), None);
+        assert_eq!(choice.check(&config, "9"), None);
+    }
+}
@@ -1,12 +1,81 @@
-use clap::{Parser, ValueEnum};
+use std::io::stdout;
+use std::path::PathBuf;
+
+use clap::{Args as ClapArgs, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+use crate::types::add::AddArgs;
+use crate::types::anki::AnkiAction;
+use crate::types::config::ConfigAction;
+use crate::types::deck::DeckAction;
+use crate::types::error::Result;
+use crate::types::exam::ExamArgs;
+use crate::types::mix::MixArgs;
+use crate::types::numbers::NumberRange;
+use crate::types::protocol::Protocol;
+use crate::types::serve::ServeArgs;
+use crate::types::tags::Level;
+use crate::types::time::TimeArgs;
+use crate::types::verbs::Tense;
 
 /// Simple program to help me learn the German language
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub(crate) struct Args {
-    /// The mode to run the program in
-    #[clap(value_enum, default_value_t = Mode::Articles)]
-    pub(crate) mode: Mode,
+    /// The mode to run the program in (articles when missing)
+    #[command(subcommand)]
+    pub(crate) mode: Option<Mode>,
+
+    /// The config file (defaults to ~/.config/practice-deutsch/config.toml)
+    #[clap(long, global = true, env = "PRACTICE_DEUTSCH_CONFIG")]
+    pub(crate) config: Option<PathBuf>,
+
+    /// The directory with the word lists and the audio files (defaults to the current directory
+    /// or ~/.local/share/practice-deutsch)
+    #[clap(long, global = true, env = "PRACTICE_DEUTSCH_DATA")]
+    pub(crate) data_dir: Option<PathBuf>,
+
+    /// The deck to practice, such as A1-kapitel-3 from lessons/A1-kapitel-3.toml
+    #[clap(long, global = true)]
+    pub(crate) deck: Option<String>,
+
+    /// Practice only the words of this level
+    #[clap(long, global = true, value_enum, ignore_case = true)]
+    pub(crate) level: Option<Level>,
+
+    /// Practice only the words with this tag, such as food (can be repeated)
+    #[clap(long, global = true)]
+    pub(crate) tag: Vec<String>,
+
+    /// Do not play the audio files
+    #[clap(long, global = true)]
+    pub(crate) no_audio: bool,
+
+    /// The playback speed of the audio files, such as 0.8 to play them slower
+    #[clap(long, global = true, value_parser = positive_speed)]
+    pub(crate) speed: Option<f64>,
+
+    /// Accept ae, oe, ue and ss for ä, ö, ü and ß in the answers
+    #[clap(long, global = true)]
+    pub(crate) umlaut_leniency: bool,
+
+    /// Do not colour the output
+    #[clap(long, global = true)]
+    pub(crate) no_colours: bool,
+
+    /// Ask line by line instead of using the full screen interface
+    #[clap(long, global = true)]
+    pub(crate) plain: bool,
+
+    /// Pick the answer from a numbered list instead of typing it, in the plural, verbs and
+    /// translation drills
+    #[clap(long, global = true)]
+    pub(crate) choices: bool,
+
+    /// Talk to another program instead, such as an editor plugin, with JSON objects on stdin and
+    /// stdout
+    #[clap(long, global = true, value_enum)]
+    pub(crate) protocol: Option<Protocol>,
 }
 
 impl Args {
@@ -15,20 +84,234 @@ impl Args {
     }
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+fn positive_speed(speed: &str) -> std::result::Result<f64, String> {
+    match speed.parse::<f64>() {
+        Ok(speed) if speed > 0.0 => Ok(speed),
+        _ => Err(format!(
+            "expected a positive number, such as 0.8, found {}",
+            speed
+        )),
+    }
+}
+
+#[derive(Subcommand, Clone, Debug)]
 pub(crate) enum Mode {
-    #[clap(name = "articles")]
-    Articles,
-    #[clap(name = "plural")]
+    /// Listen to the noun and type its article
+    Articles(ArticlesArgs),
+    /// Type the plural of the noun
     Plural,
-    #[clap(name = "verbs")]
-    Verbs,
-    #[clap(name = "alphabet")]
-    Alphabet,
-    #[clap(name = "numbers")]
-    Numbers,
-    #[clap(name = "play")]
+    /// Type the conjugation of the verb for each pronoun
+    Verbs(VerbsArgs),
+    /// Fill the gap in a sentence with the conjugated verb
+    Sentences,
+    /// Type the German noun for the English one
+    Translation,
+    /// Listen to a noun or a phrase, without its text, and type or pick its English meaning
+    Listen,
+    /// Listen to the letter and type it
+    Alphabet(AlphabetArgs),
+    /// Listen to the number and type it
+    Numbers(NumbersArgs),
+    /// Write the time or the date in words, such as halb drei, or the words in digits
+    Time(TimeArgs),
+    /// Answer a fixed number of questions from all the drills against the clock, once each
+    Exam(ExamArgs),
+    /// Interleave the articles, plurals, verbs, numbers and letters, favouring the weak and due items
+    Mix(MixArgs),
+    /// Listen to the noun or phrase, say it and compare the recording with the original
+    Speak(SpeakArgs),
+    /// Serve the drills, the audio files and the word lists to a browser on localhost
+    Serve(ServeArgs),
+    /// Play a phrase with an online text to speech service
     Play,
-    #[clap(name = "download")]
-    Download,
+    /// Download the missing audio files
+    Download(DownloadArgs),
+    /// Add a noun, verb or phrase to the word lists and fetch its audio files
+    Add(AddArgs),
+    /// Export the word lists to Anki or import the new notes from Anki
+    Anki {
+        #[command(subcommand)]
+        action: AnkiAction,
+    },
+    /// List the nouns that break their suffix rule
+    Audit,
+    /// Check the word lists for problems
+    Lint(LintArgs),
+    /// List the decks in the lessons directory
+    Decks {
+        #[command(subcommand)]
+        action: Option<DeckAction>,
+    },
+    /// Show the accuracy of the answers per mode and per tag
+    Stats,
+    /// Show the settings from the config file
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+    /// Print the shell completion script
+    Completions {
+        /// The shell to generate the script for
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct ArticlesArgs {
+    /// The number of nouns to practice
+    #[clap(long)]
+    pub(crate) count: Option<usize>,
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct VerbsArgs {
+    /// The tenses to practice, such as praesens,perfekt
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub(crate) tense: Vec<Tense>,
+
+    /// Ask each distinct conjugation once, with a random pronoun that shares it
+    #[clap(long)]
+    pub(crate) distinct: bool,
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct NumbersArgs {
+    /// The numbers to practice, such as 1..100
+    #[clap(long)]
+    pub(crate) range: Option<NumberRange>,
+}
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct AlphabetArgs {
+    /// Tell apart the letters that sound alike, such as e and i, the ones you confuse most often
+    /// first
+    #[clap(long, conflicts_with = "spell")]
+    pub(crate) pairs: bool,
+
+    /// Listen to a noun spelled letter by letter and type it
+    #[clap(long)]
+    pub(crate) spell: bool,
+
+    /// Spell German names instead of nouns, as on the phone
+    #[clap(long, requires = "spell")]
+    pub(crate) names: bool,
+
+    /// Spell with the DIN 5009 spelling alphabet, as in B wie Berta
+    #[clap(long, requires = "spell")]
+    pub(crate) din: bool,
+
+    /// The number of letters (or words) to practice with --pairs or --spell
+    #[clap(long, default_value_t = 20)]
+    pub(crate) count: usize,
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct SpeakArgs {
+    /// A WAV file to use instead of recording the microphone
+    #[clap(long)]
+    pub(crate) input: Option<PathBuf>,
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct DownloadArgs {
+    /// Where to download the audio files from (all of them when missing)
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub(crate) provider: Vec<Provider>,
+
+    /// List the missing audio files without downloading them
+    #[clap(long)]
+    pub(crate) dry_run: bool,
+}
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct LintArgs {
+    /// Apply the safe corrections to the CSV files
+    #[clap(long)]
+    pub(crate) fix: bool,
+}
+
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Provider {
+    /// The nouns from verbformen.de
+    #[clap(name = "verbformen")]
+    Verbformen,
+    /// The plural nouns from collinsdictionary.com
+    #[clap(name = "collins")]
+    Collins,
+    /// The nouns and verbs recorded by hand with naturalreaders.com
+    #[clap(name = "manual")]
+    Manual,
+}
+
+pub(crate) fn completions(shell: Shell) -> Result<()> {
+    let mut command = Args::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut stdout());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, Parser};
+
+    use crate::types::add::Entry;
+    use crate::types::cla::{Args, Mode, Provider};
+    use crate::types::numbers::NumberRange;
+    use crate::types::tags::Level;
+
+    #[test]
+    fn valid_command() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn per_mode_options() {
+        let args = Args::parse_from(["practice-deutsch", "numbers", "--range", "1..1000"]);
+        let Some(Mode::Numbers(numbers)) = args.mode else {
+            panic!("Expected the numbers mode");
+        };
+        assert_eq!(numbers.range, Some(NumberRange::new(1, 1000)));
+
+        let args = Args::parse_from([
+            "practice-deutsch",
+            "download",
+            "--provider",
+            "collins",
+            "--dry-run",
+            "--no-audio",
+        ]);
+        let Some(Mode::Download(download)) = args.mode else {
+            panic!("Expected the download mode");
+        };
+        assert_eq!(download.provider, vec![Provider::Collins]);
+        assert!(download.dry_run);
+        assert!(args.no_audio);
+
+        assert!(Args::try_parse_from(["practice-deutsch", "plural", "--count", "5"]).is_err());
+
+        let args = Args::parse_from([
+            "practice-deutsch",
+            "add",
+            "verb",
+            "--german",
+            "wohnen",
+            "--english",
+            "to live",
+            "--present",
+            "wohne,wohnst,wohnt,wohnen,wohnt,wohnen",
+            "--level",
+            "A1",
+            "--no-download",
+        ]);
+        let Some(Mode::Add(add)) = args.mode else {
+            panic!("Expected the add mode");
+        };
+        let Entry::Verb(verb) = add.entry else {
+            panic!("Expected a verb");
+        };
+        assert_eq!(verb.present.len(), 6);
+        assert!(add.no_download);
+        assert_eq!(args.level, Some(Level::A1));
+    }
 }
@@ -0,0 +1,305 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use crate::types::audio::configure_playback;
+use crate::types::cla::{Args, Mode};
+use crate::types::data::DataDir;
+use crate::types::error::{Error, Result};
+use crate::types::numbers::NumberRange;
+use crate::types::tags::Level;
+use crate::types::utils::spell_out_umlauts;
+use crate::types::verbs::Tense;
+
+const CONFIG_FILE: &str = "practice-deutsch/config.toml";
+
+/// The settings read from the config file, with the command line flags applied on top
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+    /// The directory with the word lists and the audio files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) data_dir: Option<PathBuf>,
+    /// The deck to practice, such as A1-kapitel-3 (all the words when missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) deck: Option<String>,
+    /// Practice only the words of this level, such as A1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) level: Option<Level>,
+    /// Practice only the words with one of these tags, such as food
+    pub(crate) tags: Vec<String>,
+    /// Whether to play the audio files
+    pub(crate) audio: bool,
+    /// The playback speed of the audio files (1.0 is the normal speed)
+    pub(crate) speed: f64,
+    /// Accept ae, oe, ue and ss for ä, ö, ü and ß in the answers
+    pub(crate) umlaut_leniency: bool,
+    /// Whether to colour the output, such as the articles
+    pub(crate) colours: bool,
+    /// Use the line based interface instead of the full screen one in the drills
+    pub(crate) plain: bool,
+    /// Pick the answer from a numbered list instead of typing it, where the drill allows it
+    pub(crate) choices: bool,
+    pub(crate) articles: ArticlesConfig,
+    pub(crate) verbs: VerbsConfig,
+    pub(crate) numbers: NumbersConfig,
+
+    /// The file these settings were read from, if any
+    #[serde(skip)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ArticlesConfig {
+    /// The number of nouns picked at random for each session
+    pub(crate) count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct VerbsConfig {
+    /// The tenses to practice, one picked at random for each verb
+    pub(crate) tenses: Vec<Tense>,
+    /// Ask each distinct conjugation once
+    pub(crate) distinct: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct NumbersConfig {
+    /// The numbers to practice, such as "1..100" (all the recorded numbers when missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) range: Option<NumberRange>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum ConfigAction {
+    /// Print the effective settings
+    Show,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            data_dir: None,
+            deck: None,
+            level: None,
+            tags: vec![],
+            audio: true,
+            speed: 1.0,
+            umlaut_leniency: false,
+            colours: true,
+            plain: false,
+            choices: false,
+            articles: ArticlesConfig::default(),
+            verbs: VerbsConfig::default(),
+            numbers: NumbersConfig::default(),
+            file: None,
+        }
+    }
+}
+
+impl Default for ArticlesConfig {
+    fn default() -> Self {
+        /* There are many nouns, and it is hard to practice and remember all */
+        ArticlesConfig { count: 25 }
+    }
+}
+
+impl Default for VerbsConfig {
+    fn default() -> Self {
+        VerbsConfig {
+            tenses: vec![Tense::Praesens],
+            distinct: false,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the given config file (from --config or PRACTICE_DEUTSCH_CONFIG), which must exist,
+    /// or ~/.config/practice-deutsch/config.toml if there is one.  The defaults are used for the
+    /// missing settings.
+    pub(crate) fn load(explicit: Option<&Path>) -> Result<Self> {
+        let file = match explicit {
+            Some(file) => file.to_path_buf(),
+            None => match default_config_file() {
+                Some(file) if file.is_file() => file,
+                _ => return Ok(Config::default()),
+            },
+        };
+
+        let content = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+        let mut config = Self::parse(&content).map_err(|e| Error::config(&file, e))?;
+        config.file = Some(file);
+        Ok(config)
+    }
+
+    fn parse(content: &str) -> std::result::Result<Self, String> {
+        let config: Config = toml::from_str(content).map_err(|e| e.message().to_string())?;
+        if config.speed <= 0.0 {
+            return Err(format!("speed must be positive, found {}", config.speed));
+        }
+        if config.verbs.tenses.is_empty() {
+            return Err("verbs.tenses must have at least one tense".to_string());
+        }
+        Ok(config)
+    }
+
+    /// The command line flags take precedence over the config file
+    pub(crate) fn with_args(mut self, args: &Args) -> Self {
+        if let Some(data_dir) = &args.data_dir {
+            self.data_dir = Some(data_dir.clone());
+        }
+        if let Some(deck) = &args.deck {
+            self.deck = Some(deck.clone());
+        }
+        if let Some(level) = args.level {
+            self.level = Some(level);
+        }
+        if !args.tag.is_empty() {
+            self.tags = args.tag.clone();
+        }
+        if args.no_audio {
+            self.audio = false;
+        }
+        if let Some(speed) = args.speed {
+            self.speed = speed;
+        }
+        if args.umlaut_leniency {
+            self.umlaut_leniency = true;
+        }
+        if args.no_colours {
+            self.colours = false;
+        }
+        if args.plain {
+            self.plain = true;
+        }
+        if args.choices {
+            self.choices = true;
+        }
+
+        match &args.mode {
+            Some(Mode::Articles(articles)) => {
+                if let Some(count) = articles.count {
+                    self.articles.count = count;
+                }
+            }
+            Some(Mode::Verbs(verbs)) => {
+                if !verbs.tense.is_empty() {
+                    self.verbs.tenses = verbs.tense.clone();
+                }
+                if verbs.distinct {
+                    self.verbs.distinct = true;
+                }
+            }
+            Some(Mode::Numbers(numbers)) => {
+                if let Some(range) = &numbers.range {
+                    self.numbers.range = Some(range.clone());
+                }
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Applies the settings that hold for the whole program, such as the audio and the colours
+    pub(crate) fn apply(&self) {
+        configure_playback(self.audio, self.speed as f32);
+        if !self.colours {
+            colored::control::set_override(false);
+        }
+    }
+
+    /// The answer as it is compared with the expected one
+    pub(crate) fn spelling(&self, answer: &str) -> String {
+        if self.umlaut_leniency {
+            spell_out_umlauts(answer)
+        } else {
+            answer.to_string()
+        }
+    }
+}
+
+pub(crate) fn config(config: &Config, action: &Option<ConfigAction>) -> Result<()> {
+    match action {
+        None | Some(ConfigAction::Show) => show(config),
+    }
+}
+
+fn show(config: &Config) -> Result<()> {
+    println!("------------------------------------------------------------");
+    match (&config.file, default_config_file()) {
+        (Some(file), _) => println!("# Read from {}", file.display()),
+        (None, Some(file)) => println!("# No {} found, using the defaults", file.display()),
+        (None, None) => println!("# No config file found, using the defaults"),
+    }
+    match DataDir::locate(config.data_dir.as_deref()) {
+        Ok(data) => println!("# Data directory: {}", data),
+        Err(e) => println!("# Data directory: {}", e),
+    }
+    println!("------------------------------------------------------------");
+
+    let content = toml::to_string(config).map_err(|e| Error::config(Path::new(CONFIG_FILE), e))?;
+    print!("{}", content);
+    Ok(())
+}
+
+/// $XDG_CONFIG_HOME/practice-deutsch/config.toml, or ~/.config/practice-deutsch/config.toml
+fn default_config_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join(CONFIG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::config::Config;
+    use crate::types::numbers::NumberRange;
+    use crate::types::verbs::Tense;
+
+    #[test]
+    fn defaults_for_missing_settings() {
+        let config = Config::parse(
+            r#"
+            audio = false
+
+            [verbs]
+            tenses = ["perfekt", "futur"]
+
+            [numbers]
+            range = "1..20"
+            "#,
+        )
+        .unwrap();
+
+        assert!(!config.audio);
+        assert_eq!(config.speed, 1.0);
+        assert_eq!(config.articles.count, 25);
+        assert_eq!(config.verbs.tenses, vec![Tense::Perfekt, Tense::Futur]);
+        assert_eq!(config.numbers.range, Some(NumberRange::new(1, 20)));
+    }
+
+    #[test]
+    fn invalid_settings() {
+        assert!(Config::parse("speed = 0").is_err());
+        assert!(Config::parse("volume = 3").is_err());
+        assert!(Config::parse("[verbs]\ntenses = []").is_err());
+        assert!(Config::parse("[verbs]\ntenses = [\"plusquamperfekt\"]").is_err());
+    }
+
+    #[test]
+    fn umlaut_leniency() {
+        let mut config = Config::default();
+        assert_eq!(config.spelling("Äpfel"), "Äpfel");
+
+        config.umlaut_leniency = true;
+        assert_eq!(config.spelling("Äpfel"), "Aepfel");
+        assert_eq!(config.spelling("Aepfel"), "Aepfel");
+    }
+}
@@ -0,0 +1,148 @@
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::types::error::{Error, Result};
+
+const APPLICATION: &str = "practice-deutsch";
+
+/* The directory is recognised by this file */
+const MARKER_FILE: &str = "nouns.csv";
+
+/// The directory with the word lists (nouns.csv, verbs.csv, phrases.csv) and the audio files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DataDir {
+    path: PathBuf,
+    /// Where the answers are written, which the data directory (such as /usr/share) may not allow
+    state: PathBuf,
+}
+
+impl DataDir {
+    /// The directory for both the word lists and the answers
+    pub(crate) fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        DataDir {
+            state: path.clone(),
+            path,
+        }
+    }
+
+    /// Writes the answers to the per-user XDG state directory instead, such as
+    /// ~/.local/state/practice-deutsch
+    fn with_user_state(self) -> Self {
+        match xdg_state_home() {
+            Some(home) => DataDir {
+                state: home.join(APPLICATION),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    /// Uses the given directory (from --data-dir or PRACTICE_DEUTSCH_DATA) if any.  Otherwise uses
+    /// the current directory if it has the word lists (as when running from the repository), or
+    /// the first XDG data directory that has them (such as ~/.local/share/practice-deutsch).
+    pub(crate) fn locate(explicit: Option<&Path>) -> Result<Self> {
+        if let Some(path) = explicit {
+            return if path.is_dir() {
+                Ok(DataDir::new(path).with_user_state())
+            } else {
+                Err(Error::DataDir(format!(
+                    "{} is not a directory",
+                    path.display()
+                )))
+            };
+        }
+
+        /* An empty path keeps the file names short in messages (nouns.csv rather than ./nouns.csv) */
+        std::iter::once(PathBuf::new())
+            .chain(xdg_data_dirs().into_iter().map(|dir| dir.join(APPLICATION)))
+            .find(|dir| dir.join(MARKER_FILE).is_file())
+            .map(|dir| DataDir::new(dir).with_user_state())
+            .ok_or_else(|| {
+                Error::DataDir(format!(
+                    "No {} found in the current directory or the XDG data directories, use --data-dir or PRACTICE_DEUTSCH_DATA",
+                    MARKER_FILE
+                ))
+            })
+    }
+
+    /// A file or directory in the data directory, such as nouns.csv
+    pub(crate) fn file(&self, name: &str) -> PathBuf {
+        self.path.join(name)
+    }
+
+    /// One of the audio directories, such as audio/nouns
+    pub(crate) fn audio(&self, directory: &str) -> PathBuf {
+        self.path.join("audio").join(directory)
+    }
+
+    /// A file written by the drills, such as history.csv
+    pub(crate) fn state(&self, name: &str) -> PathBuf {
+        self.state.join(name)
+    }
+}
+
+impl Display for DataDir {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.path.as_os_str().is_empty() {
+            write!(f, "the current directory")
+        } else {
+            write!(f, "{}", self.path.display())
+        }
+    }
+}
+
+/// $XDG_STATE_HOME, or ~/.local/state as in the XDG Base Directory Specification
+fn xdg_state_home() -> Option<PathBuf> {
+    env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/state")))
+}
+
+/// $XDG_DATA_HOME followed by $XDG_DATA_DIRS, with the defaults from the XDG Base Directory
+/// Specification
+fn xdg_data_dirs() -> Vec<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(
+            data_dirs
+                .split(':')
+                .map(PathBuf::from)
+                .filter(|path| path.is_absolute()),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::types::data::DataDir;
+
+    #[test]
+    fn paths_in_data_dir() {
+        let data = DataDir::new("/data");
+        assert_eq!(data.file("nouns.csv"), Path::new("/data/nouns.csv"));
+        assert_eq!(data.audio("nouns"), Path::new("/data/audio/nouns"));
+        assert_eq!(data.state("history.csv"), Path::new("/data/history.csv"));
+    }
+
+    #[test]
+    fn locate_in_current_directory() {
+        let data = DataDir::locate(None).unwrap();
+        assert_eq!(data.file("nouns.csv"), Path::new("nouns.csv"));
+        assert_eq!(data.to_string(), "the current directory");
+    }
+}
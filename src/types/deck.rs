@@ -0,0 +1,291 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use serde::Deserialize;
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::{Error, Result};
+use crate::types::nouns::Noun;
+use crate::types::phrases::{Phrase, Phrases};
+use crate::types::progress::History;
+use crate::types::tags::{Level, Tagged};
+use crate::types::verbs::Verb;
+
+/// The directory (in the data directory) with the decks, such as lessons/A1-kapitel-3.toml
+const LESSONS_DIR: &str = "lessons";
+
+/// A named subset of the nouns, verbs and phrases, such as the words of one textbook chapter
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Deck {
+    /// The file name without the extension
+    #[serde(skip)]
+    pub(crate) name: String,
+    /// A short description, such as the textbook and the chapter
+    pub(crate) description: Option<String>,
+    /// The singular of the nouns, such as Apfel
+    pub(crate) nouns: Vec<String>,
+    /// The infinitive of the verbs, without sich, such as essen
+    pub(crate) verbs: Vec<String>,
+    /// The German phrases, as written in phrases.csv
+    pub(crate) phrases: Vec<String>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum DeckAction {
+    /// List the decks with their sizes and progress
+    List,
+}
+
+impl Deck {
+    /// Reads the deck with the given name from the lessons directory, or from the given file when
+    /// the name ends with .toml
+    pub(crate) fn load(data: &DataDir, name: &str) -> Result<Self> {
+        let file = if name.ends_with(".toml") {
+            PathBuf::from(name)
+        } else {
+            data.file(LESSONS_DIR).join(format!("{}.toml", name))
+        };
+        Self::read(&file)
+    }
+
+    fn read(file: &Path) -> Result<Self> {
+        let content = fs::read_to_string(file).map_err(|e| Error::io(file, e))?;
+        let mut deck: Deck =
+            toml::from_str(&content).map_err(|e| Error::deck(file, e.message()))?;
+        deck.name = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(deck)
+    }
+
+    /// All the decks in the lessons directory, sorted by name
+    fn list(data: &DataDir) -> Result<Vec<Self>> {
+        let directory = data.file(LESSONS_DIR);
+        if !directory.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut decks = fs::read_dir(&directory)
+            .map_err(|e| Error::io(&directory, e))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<PathBuf>>>()
+            .map_err(|e| Error::io(&directory, e))?
+            .into_iter()
+            .filter(|file| {
+                file.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .map(|file| Self::read(&file))
+            .collect::<Result<Vec<Deck>>>()?;
+        decks.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(decks)
+    }
+
+    fn has_noun(&self, noun: &Noun) -> bool {
+        self.nouns.contains(&noun.singular)
+    }
+
+    fn has_verb(&self, verb: &Verb) -> bool {
+        self.verbs.contains(&verb.german)
+    }
+
+    fn has_phrase(&self, phrase: &Phrase) -> bool {
+        self.phrases.contains(&phrase.german)
+    }
+}
+
+/// The items to practice: those of the deck chosen with --deck (or in the config file) that have
+/// the level and one of the tags chosen with --level and --tag, or all of them
+#[derive(Debug, Default)]
+pub(crate) struct Selection {
+    deck: Option<Deck>,
+    level: Option<Level>,
+    tags: Vec<String>,
+}
+
+impl Selection {
+    pub(crate) fn new(data: &DataDir, config: &Config) -> Result<Self> {
+        let deck = match &config.deck {
+            Some(name) => Some(Deck::load(data, name)?),
+            None => None,
+        };
+        Ok(Selection {
+            deck,
+            level: config.level,
+            tags: config.tags.clone(),
+        })
+    }
+
+    pub(crate) fn nouns(&self, data: &DataDir) -> Result<Vec<Noun>> {
+        let mut nouns = Noun::read(data)?;
+        nouns.retain(|noun| {
+            self.deck.as_ref().is_none_or(|deck| deck.has_noun(noun)) && self.includes(noun)
+        });
+        Ok(nouns)
+    }
+
+    pub(crate) fn verbs(&self, data: &DataDir) -> Result<Vec<Verb>> {
+        let mut verbs = Verb::read(data)?;
+        verbs.retain(|verb| {
+            self.deck.as_ref().is_none_or(|deck| deck.has_verb(verb)) && self.includes(verb)
+        });
+        Ok(verbs)
+    }
+
+    /* The phrases are filtered by reference, as Phrases may write all of them back to the file */
+    pub(crate) fn phrases<'a>(&self, phrases: &'a Phrases) -> Vec<&'a Phrase> {
+        phrases
+            .iter()
+            .filter(|phrase| {
+                self.deck
+                    .as_ref()
+                    .is_none_or(|deck| deck.has_phrase(phrase))
+                    && self.includes(*phrase)
+            })
+            .collect()
+    }
+
+    fn includes(&self, item: &impl Tagged) -> bool {
+        self.level.is_none_or(|level| item.level() == Some(level))
+            && (self.tags.is_empty() || self.tags.iter().any(|tag| item.has_tag(tag)))
+    }
+}
+
+pub(crate) fn decks(data: &DataDir, action: &Option<DeckAction>) -> Result<()> {
+    match action {
+        None | Some(DeckAction::List) => list(data),
+    }
+}
+
+fn list(data: &DataDir) -> Result<()> {
+    let decks = Deck::list(data)?;
+    if decks.is_empty() {
+        println!("No decks found in {}", data.file(LESSONS_DIR).display());
+        return Ok(());
+    }
+
+    let nouns = Noun::read(data)?;
+    let verbs = Verb::read(data)?;
+    let phrases = Phrases::read(data)?;
+    let history = History::read(data)?;
+
+    for deck in decks.iter() {
+        println!("------------------------------------------------------------");
+        match &deck.description {
+            Some(description) => println!("{} ({})", deck.name, description),
+            None => println!("{}", deck.name),
+        }
+
+        let mut missing: Vec<&str> = Vec::new();
+
+        let mut learned = 0;
+        for singular in deck.nouns.iter() {
+            if nouns.iter().any(|noun| &noun.singular == singular) {
+                learned += history.learned("articles", singular) as usize;
+            } else {
+                missing.push(singular);
+            }
+        }
+        print_size("nouns", deck.nouns.len(), learned);
+
+        let mut learned = 0;
+        for german in deck.verbs.iter() {
+            if verbs.iter().any(|verb| &verb.german == german) {
+                learned += history.learned("verbs", german) as usize;
+            } else {
+                missing.push(german);
+            }
+        }
+        print_size("verbs", deck.verbs.len(), learned);
+
+        let mut learned = 0;
+        for german in deck.phrases.iter() {
+            if phrases.iter().any(|phrase| &phrase.german == german) {
+                learned += history.learned_variant("sentences", german) as usize;
+            } else {
+                missing.push(german);
+            }
+        }
+        print_size("phrases", deck.phrases.len(), learned);
+
+        if !missing.is_empty() {
+            println!("  not in the word lists: {}", missing.join(", "));
+        }
+    }
+    println!("------------------------------------------------------------");
+
+    Ok(())
+}
+
+fn print_size(kind: &str, size: usize, learned: usize) {
+    if size > 0 {
+        println!("  {:<8} {:>4} ({} learned)", kind, size, learned);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::data::DataDir;
+    use crate::types::deck::{Deck, Selection};
+    use crate::types::phrases::{Phrase, Phrases};
+    use crate::types::tags::Level;
+
+    #[test]
+    fn select_deck_items() {
+        let data = DataDir::new("");
+        let deck: Deck = toml::from_str(
+            r#"
+            nouns = ["Apfel", "Unbekannt"]
+            verbs = ["wohnen"]
+            "#,
+        )
+        .unwrap();
+        let selection = Selection {
+            deck: Some(deck),
+            ..Selection::default()
+        };
+
+        let nouns = selection.nouns(&data).unwrap();
+        assert_eq!(nouns.len(), 1);
+        assert_eq!(nouns[0].singular, "Apfel");
+
+        let verbs = selection.verbs(&data).unwrap();
+        assert_eq!(verbs.len(), 1);
+
+        let phrases = Phrases::read(&data).unwrap();
+        assert!(selection.phrases(&phrases).is_empty());
+        assert_eq!(
+            Selection::default().phrases(&phrases).len(),
+            phrases.iter().count()
+        );
+    }
+
+    #[test]
+    fn select_level_and_tags() {
+        let mut phrase = Phrase::new(
+            "Ich esse gern Äpfel.".to_string(),
+            "I like to eat apples.".to_string(),
+        );
+        phrase.tags = Some("food fruit".to_string());
+        phrase.level = Some(Level::A1);
+
+        let selection = |level: Option<Level>, tags: &[&str]| Selection {
+            deck: None,
+            level,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        assert!(selection(None, &[]).includes(&phrase));
+        assert!(selection(Some(Level::A1), &["drinks", "fruit"]).includes(&phrase));
+        assert!(!selection(Some(Level::A2), &[]).includes(&phrase));
+        assert!(!selection(None, &["drinks"]).includes(&phrase));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        assert!(toml::from_str::<Deck>("adjectives = [\"gut\"]").is_err());
+    }
+}
@@ -1,4 +1,3 @@
-use std::error::Error;
 use std::fs::File;
 use std::io::Cursor;
 use std::path::Path;
@@ -6,21 +5,71 @@ use std::thread::sleep;
 use std::time::Duration;
 use std::{fs, io};
 
+use crate::types::cla::{DownloadArgs, Provider};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::{Error, Result};
 use crate::types::nouns::Noun;
+use crate::types::phrases::Phrase;
+use crate::types::verbs::Verb;
 
-pub(crate) fn download() {
-    download_missing_nouns_from_verbformen();
-    download_missing_nouns_from_collins_dictionary();
-    manual::download_missing_nouns();
-    manual::download_missing_verbs();
-    // satzapp::download_missing_phrases();
+pub(crate) fn download(data: &DataDir, config: &Config, options: &DownloadArgs) -> Result<()> {
+    let selection = Selection::new(data, config)?;
+    let dry_run = options.dry_run;
+    let uses =
+        |provider: Provider| options.provider.is_empty() || options.provider.contains(&provider);
+
+    if uses(Provider::Verbformen) {
+        download_missing_nouns_from_verbformen(data, &selection, dry_run)?;
+    }
+    if uses(Provider::Collins) {
+        download_missing_nouns_from_collins_dictionary(data, &selection, dry_run)?;
+    }
+    if uses(Provider::Manual) {
+        manual::download_missing_nouns(data, &selection, dry_run)?;
+        manual::download_missing_verbs(data, &selection, dry_run)?;
+        manual::download_missing_spelling_alphabet(data, dry_run)?;
+        manual::download_missing_time_words(data, dry_run)?;
+    }
+    // satzapp::download_missing_phrases(data)?;
     println!("Done");
+    Ok(())
+}
+
+/// Downloads (or records) the missing audio files of one noun, such as one that was just added
+pub(crate) fn download_noun(data: &DataDir, noun: &Noun) -> Result<()> {
+    download_noun_from_verbformen(data, noun, false);
+    download_noun_from_collins_dictionary(data, noun, false);
+    manual::download_noun(data, noun, false)
+}
+
+/// Records the missing audio files of one verb, such as one that was just added
+pub(crate) fn download_verb(data: &DataDir, verb: &Verb) -> Result<()> {
+    manual::download_verb(data, verb, false)
+}
+
+/// Records the missing audio file of one phrase, such as one that was just added
+pub(crate) fn download_phrase(data: &DataDir, phrase: &Phrase) -> Result<()> {
+    manual::download_phrase(data, phrase, false)
 }
 
-fn download_missing_nouns_from_verbformen() {
+fn download_missing_nouns_from_verbformen(
+    data: &DataDir,
+    selection: &Selection,
+    dry_run: bool,
+) -> Result<()> {
     println!("Downloading missing nouns from verbformen.de");
 
-    fn download_missing_noun(file: &Path, noun: &str) {
+    for noun in selection.nouns(data)? {
+        download_noun_from_verbformen(data, &noun, dry_run);
+    }
+
+    Ok(())
+}
+
+fn download_noun_from_verbformen(data: &DataDir, noun: &Noun, dry_run: bool) {
+    let download_missing_noun = |file: &Path, noun: &str| {
         if file.exists() {
             return;
         }
@@ -34,156 +83,252 @@ fn download_missing_nouns_from_verbformen() {
             .replace("ü", "u3")
             .replace("ß", "s5");
 
-        if let Err(_) = download_file(
-            &format!(
-                "https://www.verbformen.de/deklination/substantive/grundform/{}.mp3",
-                link_noun
-            ),
-            &file,
-        ) {
+        let link = format!(
+            "https://www.verbformen.de/deklination/substantive/grundform/{}.mp3",
+            link_noun
+        );
+        if dry_run {
+            print_missing(&link, file);
+            return;
+        }
+
+        if download_file(&link, file).is_err() {
             println!("Failed to download audio file from: {}", link_noun);
         }
 
         sleep(Duration::from_secs(1));
-    }
+    };
 
-    for noun in Noun::read() {
-        download_missing_noun(&noun.singular_file_path(), &noun.singular);
-        download_missing_noun(
-            &noun.singular_with_article_file_path(),
-            &format!("der_{}", &noun.singular),
-        );
-    }
+    download_missing_noun(&noun.singular_file_path(data), &noun.singular);
+    download_missing_noun(
+        &noun.singular_with_article_file_path(data),
+        &format!("der_{}", &noun.singular),
+    );
 }
 
-fn download_missing_nouns_from_collins_dictionary() {
+fn download_missing_nouns_from_collins_dictionary(
+    data: &DataDir,
+    selection: &Selection,
+    dry_run: bool,
+) -> Result<()> {
     println!("Downloading missing nouns from collinsdictionary.com");
 
-    for noun in Noun::read() {
-        if let None = noun.plural {
-            continue;
-        }
+    for noun in selection.nouns(data)? {
+        download_noun_from_collins_dictionary(data, &noun, dry_run);
+    }
 
-        let file = noun.plural_file_path();
-        if file.is_file() {
-            continue;
-        }
+    Ok(())
+}
 
-        let link_noun = noun
-            .plural
-            .clone()
-            .unwrap()
-            .replace("Ä", "A")
-            .replace("Ö", "O")
-            .replace("Ü", "U")
-            .replace("ä", "a")
-            .replace("ö", "o")
-            .replace("ü", "u")
-            .to_lowercase();
-
-        if let Err(_) = download_file(
-            &format!(
-                "https://www.collinsdictionary.com/sounds/hwd_sounds/de_{}.mp3",
-                link_noun
-            ),
-            &file,
-        ) {
-            println!("Failed to download noun {}", &noun.singular);
-        }
+fn download_noun_from_collins_dictionary(data: &DataDir, noun: &Noun, dry_run: bool) {
+    let Some(plural) = &noun.plural else {
+        return;
+    };
 
-        sleep(Duration::from_secs(1));
+    let file = noun.plural_file_path(data);
+    if file.is_file() {
+        return;
+    }
+
+    let link_noun = plural
+        .replace("Ä", "A")
+        .replace("Ö", "O")
+        .replace("Ü", "U")
+        .replace("ä", "a")
+        .replace("ö", "o")
+        .replace("ü", "u")
+        .to_lowercase();
+
+    let link = format!(
+        "https://www.collinsdictionary.com/sounds/hwd_sounds/de_{}.mp3",
+        link_noun
+    );
+    if dry_run {
+        print_missing(&link, &file);
+        return;
+    }
+
+    if download_file(&link, &file).is_err() {
+        println!("Failed to download noun {}", &noun.singular);
     }
+
+    sleep(Duration::from_secs(1));
 }
 
 mod manual {
     use std::fs::File;
     use std::io::{Read, Write};
-    use std::path::PathBuf;
+    use std::path::Path;
 
     use base64::{engine::general_purpose, Engine as _};
 
+    use crate::types::alphabet::spelling_alphabet;
     use crate::types::audio::play_file;
+    use crate::types::data::DataDir;
+    use crate::types::deck::Selection;
+    use crate::types::error::{Error, Result};
     use crate::types::nouns::Noun;
+    use crate::types::phrases::Phrase;
+    use crate::types::time::time_recordings;
     use crate::types::utils::read_line;
-    use crate::types::verbs::{Pronoun, Verb};
+    use crate::types::verbs::{Tense, Verb};
 
-    pub(super) fn download_missing_nouns() {
+    pub(super) fn download_missing_nouns(
+        data: &DataDir,
+        selection: &Selection,
+        dry_run: bool,
+    ) -> Result<()> {
         println!(
             "Downloading missing nouns manually (from: https://www.naturalreaders.com/online/)"
         );
 
-        for noun in Noun::read() {
-            download_manually(&noun.singular, &noun.singular_file_path());
-            download_manually(
-                &format!("{} {}", &noun.article, &noun.singular),
-                &noun.singular_with_article_file_path(),
-            );
-            if let Some(plural) = &noun.plural {
-                download_manually(&plural, &noun.plural_file_path());
-                download_manually(
-                    &format!("die {}", &plural),
-                    &noun.plural_with_article_file_path(),
-                );
-            };
+        for noun in selection.nouns(data)? {
+            download_noun(data, &noun, dry_run)?;
         }
+
+        Ok(())
     }
 
-    pub(super) fn download_missing_verbs() {
+    pub(super) fn download_noun(data: &DataDir, noun: &Noun, dry_run: bool) -> Result<()> {
+        download_manually(&noun.singular, &noun.singular_file_path(data), dry_run)?;
+        download_manually(
+            &format!("{} {}", &noun.article, &noun.singular),
+            &noun.singular_with_article_file_path(data),
+            dry_run,
+        )?;
+        if let Some(plural) = &noun.plural {
+            download_manually(plural, &noun.plural_file_path(data), dry_run)?;
+            download_manually(
+                &format!("die {}", &plural),
+                &noun.plural_with_article_file_path(data),
+                dry_run,
+            )?;
+        };
+
+        Ok(())
+    }
+
+    pub(super) fn download_missing_verbs(
+        data: &DataDir,
+        selection: &Selection,
+        dry_run: bool,
+    ) -> Result<()> {
         println!(
             "Downloading missing verbs manually (from: https://www.naturalreaders.com/online/)"
         );
 
-        for verb in Verb::read() {
-            download_manually(&verb.infinitive(), &verb.infinitive_audio_file_path());
-            for pronoun in Pronoun::iter() {
-                download_manually(
-                    &verb.pronoun_conjugation(pronoun),
-                    &verb.conjugation_audio_file_path(pronoun),
-                );
+        for verb in selection.verbs(data)? {
+            download_verb(data, &verb, dry_run)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn download_verb(data: &DataDir, verb: &Verb, dry_run: bool) -> Result<()> {
+        download_manually(
+            &verb.infinitive(),
+            &verb.infinitive_audio_file_path(data),
+            dry_run,
+        )?;
+        for tense in Tense::iter() {
+            for pronoun in tense.pronouns() {
+                if let (Some(text), Some(file)) = (
+                    verb.pronoun_conjugation(pronoun, tense),
+                    verb.conjugation_audio_file_path(data, pronoun, tense),
+                ) {
+                    download_manually(&text, &file, dry_run)?;
+                }
             }
         }
+
+        Ok(())
     }
 
-    fn download_manually(text: &String, file: &PathBuf) {
+    pub(super) fn download_missing_spelling_alphabet(data: &DataDir, dry_run: bool) -> Result<()> {
+        println!(
+            "Downloading missing spelling alphabet manually (from: https://www.naturalreaders.com/online/)"
+        );
+
+        for (text, file) in spelling_alphabet(data) {
+            if !dry_run {
+                super::create_parent_directory_if_missing(&file)?;
+            }
+            download_manually(&text, &file, dry_run)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn download_missing_time_words(data: &DataDir, dry_run: bool) -> Result<()> {
+        println!(
+            "Downloading missing times and dates manually (from: https://www.naturalreaders.com/online/)"
+        );
+
+        for (text, file) in time_recordings(data) {
+            if !dry_run {
+                super::create_parent_directory_if_missing(&file)?;
+            }
+            download_manually(&text, &file, dry_run)?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn download_phrase(data: &DataDir, phrase: &Phrase, dry_run: bool) -> Result<()> {
+        download_manually(&phrase.german, &phrase.audio_file_path(data), dry_run)
+    }
+
+    fn download_manually(text: &str, file: &Path, dry_run: bool) -> Result<()> {
         if file.exists() {
-            return;
+            return Ok(());
+        }
+
+        if dry_run {
+            println!("Missing {} (record: {})", file.display(), text);
+            return Ok(());
         }
 
-        let temp_base64_file = "target/tmp.base64";
-        File::create(temp_base64_file).unwrap();
+        let temp_base64_file = Path::new("target/tmp.base64");
+        File::create(temp_base64_file).map_err(|e| Error::io(temp_base64_file, e))?;
 
-        let file_name = &file.file_name().unwrap().to_str().unwrap();
-        read_line(&format!("{} ({})", text, file_name));
+        let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+        read_line(&format!("{} ({})", text, file_name))?;
 
         let mut base64 = String::new();
         File::open(temp_base64_file)
-            .unwrap()
-            .read_to_string(&mut base64)
-            .unwrap();
+            .and_then(|mut f| f.read_to_string(&mut base64))
+            .map_err(|e| Error::io(temp_base64_file, e))?;
 
+        let link = file.display().to_string();
         if base64.is_empty() {
-            panic!("No base64 string found");
+            return Err(Error::download(&link, "No base64 string found"));
         }
 
-        let bytes = general_purpose::STANDARD.decode(base64).unwrap();
-        let mut audio_file = File::create(file).unwrap();
-        audio_file.write_all(&bytes).unwrap();
+        let bytes = general_purpose::STANDARD
+            .decode(base64)
+            .map_err(|e| Error::download(&link, e))?;
+        File::create(file)
+            .and_then(|mut audio_file| audio_file.write_all(&bytes))
+            .map_err(|e| Error::io(file, e))?;
 
-        play_file(file).unwrap();
+        play_file(file)
     }
 }
 
+#[allow(dead_code)]
 mod satzapp {
     use std::thread::sleep;
 
     use scraper::Node::Element;
 
+    use crate::types::data::DataDir;
+    use crate::types::error::Result;
     use crate::types::nouns::Noun;
     use crate::types::phrases::{Phrase, Phrases};
 
     pub(super) fn download() {
         let text = "Ananas";
-        let content = request_phrases(&text);
+        let content = request_phrases(text);
 
         let document = scraper::Html::parse_document(&content);
         let selector = scraper::Selector::parse("hr").unwrap();
@@ -203,12 +348,12 @@ mod satzapp {
         }
     }
 
-    pub(super) fn download_missing_phrases() {
+    pub(super) fn download_missing_phrases(data: &DataDir) -> Result<()> {
         println!("Downloading missing phrases from satzapp.com");
 
-        let mut phrases = Phrases::read();
+        let mut phrases = Phrases::read(data)?;
 
-        for noun in Noun::read() {
+        for noun in Noun::read(data)? {
             println!("Downloading phrases for {}", noun.singular);
 
             let text = noun.singular;
@@ -221,10 +366,12 @@ mod satzapp {
                 text
             );
             phrases.append(&mut new_phrases);
-            phrases.write();
+            phrases.write()?;
 
             sleep(std::time::Duration::from_secs(1));
         }
+
+        Ok(())
     }
 
     fn request_phrases(noun: &str) -> String {
@@ -259,7 +406,7 @@ mod satzapp {
         response.text().unwrap()
     }
 
-    fn parse_phrases(content: &String) -> Vec<Phrase> {
+    fn parse_phrases(content: &str) -> Vec<Phrase> {
         fn parse(document: &scraper::Html, css_selector: &str) -> Vec<String> {
             let selector = scraper::Selector::parse(css_selector).unwrap();
             document
@@ -294,26 +441,29 @@ mod satzapp {
     }
 }
 
-fn download_file(link: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+fn print_missing(link: &str, path: &Path) {
+    println!("Missing {} (from: {})", path.display(), link);
+}
+
+fn download_file(link: &str, path: &Path) -> Result<()> {
     println!("Downloading audio from {} to {}", link, path.display());
 
-    let response = reqwest::blocking::get(link)?;
+    let response = reqwest::blocking::get(link).map_err(|e| Error::download(link, e))?;
 
     if response.status().is_success() {
-        let mut content = Cursor::new(response.bytes()?);
-        let mut file = File::create(path)?;
+        let mut content = Cursor::new(response.bytes().map_err(|e| Error::download(link, e))?);
         create_parent_directory_if_missing(path)?;
-        io::copy(&mut content, &mut file)?;
+        let mut file = File::create(path).map_err(|e| Error::io(path, e))?;
+        io::copy(&mut content, &mut file).map_err(|e| Error::io(path, e))?;
         Ok(())
     } else {
-        Err(Box::from("Failed to download audio file"))
+        Err(Error::download(link, response.status()))
     }
 }
 
-fn create_parent_directory_if_missing(path: &Path) -> Result<(), Box<dyn Error>> {
-    match path.parent() {
-        Some(parent) => fs::create_dir_all(parent)?,
-        None => {}
+fn create_parent_directory_if_missing(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?
     };
 
     Ok(())
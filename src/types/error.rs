@@ -0,0 +1,153 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while running one of the modes
+#[derive(Debug)]
+pub(crate) enum Error {
+    /// A CSV file (or one of its rows) could not be read
+    Csv {
+        file: PathBuf,
+        line: Option<u64>,
+        message: String,
+    },
+    /// A file or directory could not be read or written
+    Io { path: PathBuf, error: io::Error },
+    /// An audio file could not be played
+    Audio { path: PathBuf, message: String },
+    /// The user input could not be read
+    Input(io::Error),
+    /// An audio file could not be downloaded
+    Download { link: String, message: String },
+    /// The browser automation failed
+    Web(String),
+    /// The word lists have problems reported by the lint mode
+    Lint(usize),
+    /// The directory with the word lists and audio files could not be found
+    DataDir(String),
+    /// The config file has invalid settings
+    Config { file: PathBuf, message: String },
+    /// A deck in the lessons directory could not be read
+    Deck { file: PathBuf, message: String },
+    /// A new entry for the word lists is invalid, such as a duplicate
+    Entry(String),
+    /// The microphone could not be recorded, or the recording could not be played
+    Recording(String),
+    /// The full screen interface could not be drawn
+    Terminal(io::Error),
+    /// The web interface could not listen on its address
+    Serve { address: String, error: io::Error },
+}
+
+impl Error {
+    pub(crate) fn csv(file: &Path, error: &csv::Error) -> Self {
+        let message = match error.kind() {
+            csv::ErrorKind::Io(error) => error.to_string(),
+            csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+            csv::ErrorKind::UnequalLengths {
+                expected_len, len, ..
+            } => format!("expected {} fields but found {}", expected_len, len),
+            _ => error.to_string(),
+        };
+
+        Error::Csv {
+            file: file.to_path_buf(),
+            line: error.position().map(|position| position.line()),
+            message,
+        }
+    }
+
+    pub(crate) fn row(file: &Path, line: u64, message: String) -> Self {
+        Error::Csv {
+            file: file.to_path_buf(),
+            line: Some(line),
+            message,
+        }
+    }
+
+    pub(crate) fn io(path: &Path, error: io::Error) -> Self {
+        Error::Io {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+
+    pub(crate) fn audio(path: &Path, error: impl Display) -> Self {
+        Error::Audio {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn config(file: &Path, error: impl Display) -> Self {
+        Error::Config {
+            file: file.to_path_buf(),
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn deck(file: &Path, error: impl Display) -> Self {
+        Error::Deck {
+            file: file.to_path_buf(),
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn recording(error: impl Display) -> Self {
+        Error::Recording(error.to_string())
+    }
+
+    pub(crate) fn serve(address: &str, error: io::Error) -> Self {
+        Error::Serve {
+            address: address.to_string(),
+            error,
+        }
+    }
+
+    pub(crate) fn download(link: &str, error: impl Display) -> Self {
+        Error::Download {
+            link: link.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Csv {
+                file,
+                line: Some(line),
+                message,
+            } => write!(f, "{} line {}: {}", file.display(), line, message),
+            Error::Csv {
+                file,
+                line: None,
+                message,
+            } => write!(f, "{}: {}", file.display(), message),
+            Error::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Error::Audio { path, message } => {
+                write!(f, "Failed to play {}: {}", path.display(), message)
+            }
+            Error::Input(error) => write!(f, "Failed to read the user input: {}", error),
+            Error::Download { link, message } => {
+                write!(f, "Failed to download {}: {}", link, message)
+            }
+            Error::Web(message) => write!(f, "Browser automation failed: {}", message),
+            Error::Lint(errors) => write!(f, "Found {} errors in the word lists", errors),
+            Error::DataDir(message) | Error::Entry(message) => write!(f, "{}", message),
+            Error::Recording(message) => write!(f, "Failed to record: {}", message),
+            Error::Terminal(error) => write!(f, "Failed to draw the terminal: {}", error),
+            Error::Serve { address, error } => {
+                write!(f, "Failed to serve on {}: {}", address, error)
+            }
+            Error::Config { file, message } | Error::Deck { file, message } => {
+                write!(f, "{}: {}", file.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,255 @@
+use std::time::{Duration, Instant};
+
+use clap::Args as ClapArgs;
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::exercise::{Exercise, Pool, MIXED};
+use crate::types::progress::History;
+use crate::types::ui::{Input, Message, Ui};
+
+/// The share of correct answers needed to pass a section, as in the Goethe exams
+const PASS_MARK: usize = 60;
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct ExamArgs {
+    /// The number of questions, shared between the sections
+    #[clap(long, default_value_t = 20)]
+    pub(crate) questions: usize,
+
+    /// The seconds to answer each question, after which the answer does not count
+    #[clap(long, default_value_t = 30)]
+    pub(crate) question_time: u64,
+
+    /// The minutes for the whole exam, after which the questions left are not asked
+    #[clap(long, default_value_t = 10)]
+    pub(crate) total_time: u64,
+}
+
+/// How a question was answered
+enum Outcome {
+    Correct,
+    Wrong(String),
+    TooSlow,
+    Unanswered,
+}
+
+/// Asks a fixed number of questions from all the sections, each once, without replaying the audio
+/// or showing the translation, and reports which sections were passed
+pub(crate) fn exam(
+    data: &DataDir,
+    config: &Config,
+    options: &ExamArgs,
+    ui: &mut dyn Ui,
+) -> Result<()> {
+    let pool = Pool::new(data, config)?;
+    let questions = questions(&pool, options.questions);
+    if questions.is_empty() {
+        return ui.finish(vec!["No questions found for the exam".into()]);
+    }
+
+    ui.start("exam questions", questions.len());
+    ui.show(
+        format!(
+            "{} seconds per question and {} minutes in total, no replay and no translation",
+            options.question_time, options.total_time
+        )
+        .into(),
+    );
+
+    let started = Instant::now();
+    let total_time = Duration::from_secs(options.total_time * 60);
+    let question_time = Duration::from_secs(options.question_time);
+    let mut outcomes: Vec<Outcome> = Vec::new();
+    for (index, question) in questions.iter().enumerate() {
+        let Some(left) = total_time.checked_sub(started.elapsed()) else {
+            ui.show("Time is up".into());
+            break;
+        };
+
+        ui.item(&format!("{}/{}", question.item(), question.variant()));
+        let prompt = Message::new(format!("[{}] ", minutes(left))).append(question.present(data));
+
+        let asked = Instant::now();
+        let outcome = loop {
+            match ui.ask(&prompt, questions.len() - index)? {
+                Input::Quit => break None,
+                Input::Skip => break Some(Outcome::Unanswered),
+                Input::Replay | Input::Slow | Input::English => {
+                    ui.help("an answer (replay and the translation are not available in the exam)")
+                }
+                Input::Answer(_) if asked.elapsed() > question_time => {
+                    break Some(Outcome::TooSlow)
+                }
+                Input::Answer(answer) => match question.check(config, &answer) {
+                    None => ui.help(question.expected()),
+                    Some(true) => break Some(Outcome::Correct),
+                    Some(false) => break Some(Outcome::Wrong(answer)),
+                },
+            }
+        };
+        let Some(outcome) = outcome else {
+            break;
+        };
+
+        /* The variant starts with the drill, such as verbs praesens ich */
+        History::record(
+            data,
+            "exam",
+            &question.item(),
+            &format!("{} {}", question.mode(), question.variant()),
+            matches!(outcome, Outcome::Correct),
+        );
+        outcomes.push(outcome);
+    }
+
+    /* The questions that were not asked count as unanswered */
+    while outcomes.len() < questions.len() {
+        outcomes.push(Outcome::Unanswered);
+    }
+
+    let report = report(questions.iter().map(|question| question.mode()), &outcomes);
+    for (section, correct, total) in report.iter() {
+        History::record(
+            data,
+            "exam report",
+            section,
+            &format!("{} of {}", correct, total),
+            passed(*correct, *total),
+        );
+    }
+
+    let mut summary = vec![Message::new(format!(
+        "Finished the exam in {}",
+        minutes(started.elapsed())
+    ))];
+    summary.extend(report.iter().map(|(section, correct, total)| {
+        Message::new(format!(
+            "{:<10} {:>2} of {:>2} correct ({:>3}%)  {}",
+            section,
+            correct,
+            total,
+            correct * 100 / total,
+            if passed(*correct, *total) {
+                "passed"
+            } else {
+                "failed"
+            }
+        ))
+    }));
+    let all_passed = report
+        .iter()
+        .all(|(_, correct, total)| passed(*correct, *total));
+    summary.push(Message::new(if all_passed {
+        "Passed the exam".to_string()
+    } else {
+        format!(
+            "Failed the exam, each section needs {}% of the answers",
+            PASS_MARK
+        )
+    }));
+    summary.extend(
+        questions
+            .iter()
+            .zip(outcomes.iter())
+            .filter_map(|(question, outcome)| {
+                let answered = match outcome {
+                    Outcome::Correct => return None,
+                    Outcome::Wrong(answer) => format!("answered {}", answer),
+                    Outcome::TooSlow => "too slow".to_string(),
+                    Outcome::Unanswered => "unanswered".to_string(),
+                };
+                Some(Message::new(format!(
+                    " - {}: {} ({})",
+                    question.item(),
+                    question.solution(),
+                    answered
+                )))
+            }),
+    );
+    ui.finish(summary)
+}
+
+/// The questions, in random order and shared between the sections (the mixed drills) that have
+/// any
+fn questions(pool: &Pool, count: usize) -> Vec<Box<dyn Exercise + '_>> {
+    let mut rng = thread_rng();
+    let mut sections = pool.exercises();
+    for section in sections.iter_mut() {
+        section.shuffle(&mut rng);
+    }
+
+    /* One question of each section in turn, so that all sections get their share */
+    sections.retain(|questions| !questions.is_empty());
+    let mut questions = Vec::new();
+    while questions.len() < count && sections.iter().any(|section| !section.is_empty()) {
+        for section in sections.iter_mut() {
+            if questions.len() < count {
+                questions.extend(section.pop());
+            }
+        }
+    }
+    questions.shuffle(&mut rng);
+    questions
+}
+
+/// The number of correct answers and of questions per section, given the section of each
+/// question, in the order of the mixed drills
+fn report(
+    sections: impl Iterator<Item = &'static str>,
+    outcomes: &[Outcome],
+) -> Vec<(&'static str, usize, usize)> {
+    let mut report: Vec<(&'static str, usize, usize)> = Vec::new();
+    for (section, outcome) in sections.zip(outcomes.iter()) {
+        let index = match report.iter().position(|(name, _, _)| *name == section) {
+            Some(index) => index,
+            None => {
+                report.push((section, 0, 0));
+                report.len() - 1
+            }
+        };
+        report[index].1 += matches!(outcome, Outcome::Correct) as usize;
+        report[index].2 += 1;
+    }
+    report.sort_by_key(|(section, _, _)| MIXED.iter().position(|name| name == section));
+    report
+}
+
+fn passed(correct: usize, total: usize) -> bool {
+    correct * 100 >= total * PASS_MARK
+}
+
+/// Such as 4:05
+fn minutes(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::types::exam::{minutes, passed, report, Outcome};
+
+    #[test]
+    fn graded_report() {
+        let sections = ["verbs", "articles", "articles", "verbs"];
+        let outcomes = vec![
+            Outcome::Correct,
+            Outcome::Correct,
+            Outcome::TooSlow,
+            Outcome::Unanswered,
+        ];
+        assert_eq!(
+            report(sections.into_iter(), &outcomes),
+            vec![("articles", 1, 2), ("verbs", 1, 2)]
+        );
+
+        assert!(passed(3, 5));
+        assert!(!passed(1, 2));
+        assert_eq!(minutes(Duration::from_secs(245)), "4:05");
+    }
+}
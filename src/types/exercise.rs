@@ -0,0 +1,385 @@
+use std::path::{Path, PathBuf};
+
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::audio::{list_audio_files_in_directory, playback_enabled, Listening};
+use crate::types::choice::MultipleChoice;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::nouns::{ArticleExercise, Noun, PluralExercise};
+use crate::types::progress::History;
+use crate::types::ui::{Input, Message, Ui};
+use crate::types::verbs::{Conjugation, Tense, Verb};
+
+/// One question of a drill, such as the article of a noun or the conjugation of a verb for one
+/// pronoun.  The drills ask them one after the other, and the exam and mix modes mix them.
+pub(crate) trait Exercise {
+    /// The drill, as recorded in the history, such as articles
+    fn mode(&self) -> &'static str;
+
+    /// What is practised, such as the noun, as recorded in the history
+    fn item(&self) -> String;
+
+    /// Which form of the item is asked, such as praesens ich, as recorded in the history
+    fn variant(&self) -> String;
+
+    /// Plays the item, if it has audio, and returns the question
+    fn present(&self, data: &DataDir) -> Message;
+
+    /// What kind of answer is expected, such as the articles der, die or das
+    fn expected(&self) -> &'static str;
+
+    /// Whether the answer is correct, or none if it cannot be the answer (such as an unknown
+    /// article)
+    fn check(&self, config: &Config, answer: &str) -> Option<bool>;
+
+    /// Shows the correct answer (with a hint when the answer was wrong) and plays it
+    fn feedback(
+        &self,
+        data: &DataDir,
+        config: &Config,
+        ui: &mut dyn Ui,
+        answer: &str,
+        correct: bool,
+    );
+
+    /// Plays the item again, slower if asked, or returns false if there is nothing to replay (or
+    /// the recording would give the answer away)
+    fn replay(&self, data: &DataDir, slow: bool) -> bool;
+
+    /// The English translation, if any
+    fn english(&self) -> Option<String>;
+
+    /// The correct answer, such as die Brücke
+    fn solution(&self) -> String;
+
+    /// The answers to pick from in the multiple choice, the correct one (as it would be typed)
+    /// first, followed by plausible wrong ones.  None if the answer can only be typed.
+    fn choices(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// How an exercise was answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Correct,
+    Wrong,
+    Skipped,
+    Quit,
+}
+
+/// The drills whose exercises are mixed by the exam and mix modes, in the order they are reported
+pub(crate) const MIXED: [&str; 5] = ["articles", "plural", "verbs", "numbers", "alphabet"];
+
+/// The words and audio files of the drills that are mixed
+pub(crate) struct Pool {
+    nouns: Vec<Noun>,
+    verbs: Vec<Verb>,
+    tenses: Vec<Tense>,
+    numbers: Vec<PathBuf>,
+    letters: Vec<PathBuf>,
+}
+
+/// Presents the exercise and asks until it is answered, replaying it or showing the translation
+/// when asked.  The answer is recorded in the history and scored, followed by the feedback.
+pub(crate) fn attempt(
+    exercise: &dyn Exercise,
+    data: &DataDir,
+    config: &Config,
+    ui: &mut dyn Ui,
+    remaining: usize,
+) -> Result<Outcome> {
+    /* The answer is picked instead of typed when asked for, and when the exercise allows it */
+    if config.choices {
+        if let Some(choice) = MultipleChoice::new(exercise, config) {
+            return ask(&choice, data, config, ui, remaining);
+        }
+    }
+    ask(exercise, data, config, ui, remaining)
+}
+
+fn ask(
+    exercise: &dyn Exercise,
+    data: &DataDir,
+    config: &Config,
+    ui: &mut dyn Ui,
+    remaining: usize,
+) -> Result<Outcome> {
+    ui.item(&format!("{}/{}", exercise.item(), exercise.variant()));
+    let prompt = exercise.present(data);
+
+    loop {
+        match ui.ask(&prompt, remaining)? {
+            Input::Quit => return Ok(Outcome::Quit),
+            Input::Skip => return Ok(Outcome::Skipped),
+            Input::Replay => {
+                if !exercise.replay(data, false) {
+                    ui.help(exercise.expected());
+                }
+            }
+            Input::Slow => {
+                if !exercise.replay(data, true) {
+                    ui.help(exercise.expected());
+                }
+            }
+            Input::English => match exercise.english() {
+                Some(english) => ui.show(english.into()),
+                None => ui.help(exercise.expected()),
+            },
+            Input::Answer(answer) => {
+                let Some(correct) = exercise.check(config, &answer) else {
+                    ui.help(exercise.expected());
+                    continue;
+                };
+
+                History::record(
+                    data,
+                    exercise.mode(),
+                    &exercise.item(),
+                    &exercise.variant(),
+                    correct,
+                );
+                ui.score(correct);
+                exercise.feedback(data, config, ui, &answer, correct);
+                return Ok(if correct {
+                    Outcome::Correct
+                } else {
+                    Outcome::Wrong
+                });
+            }
+        }
+    }
+}
+
+impl Pool {
+    /// The selected nouns and verbs, and the numbers and letters when the audio is played
+    pub(crate) fn new(data: &DataDir, config: &Config) -> Result<Self> {
+        let selection = Selection::new(data, config)?;
+        let tenses = config.verbs.tenses.clone();
+        let mut verbs = selection.verbs(data)?;
+        verbs.retain(|verb| tenses.iter().any(|tense| verb.has_tense(tense)));
+
+        /* The numbers and the letters can only be asked by playing them */
+        let (mut numbers, mut letters) = (vec![], vec![]);
+        if playback_enabled() {
+            let range = config.numbers.range.as_ref();
+            numbers = audio_files(&data.audio("numbers"), |stem| {
+                match (range, stem.parse::<u32>()) {
+                    (None, number) => number.is_ok(),
+                    (Some(range), Ok(number)) => range.contains(number),
+                    (Some(_), Err(_)) => false,
+                }
+            })?;
+            letters = audio_files(&data.audio("alphabet"), |_| true)?;
+        }
+
+        Ok(Pool {
+            nouns: selection.nouns(data)?,
+            verbs,
+            tenses,
+            numbers,
+            letters,
+        })
+    }
+
+    /// The exercises of each drill, in the order of `MIXED`, with one conjugation (in a random
+    /// tense, for a random pronoun) per verb
+    pub(crate) fn exercises(&self) -> Vec<Vec<Box<dyn Exercise + '_>>> {
+        let mut rng = thread_rng();
+
+        vec![
+            self.nouns
+                .iter()
+                .map(|noun| Box::new(ArticleExercise(noun)) as Box<dyn Exercise>)
+                .collect(),
+            self.nouns
+                .iter()
+                .filter(|noun| noun.plural.is_some())
+                .map(|noun| Box::new(PluralExercise(noun)) as Box<dyn Exercise>)
+                .collect(),
+            self.verbs
+                .iter()
+                .filter_map(|verb| {
+                    let tenses: Vec<&Tense> = self
+                        .tenses
+                        .iter()
+                        .filter(|tense| verb.has_tense(tense))
+                        .collect();
+                    let tense = *tenses.choose(&mut rng)?;
+                    let pronoun = *tense.pronouns().collect::<Vec<_>>().choose(&mut rng)?;
+                    Some(Box::new(Conjugation::standalone(verb, tense, pronoun))
+                        as Box<dyn Exercise>)
+                })
+                .collect(),
+            self.numbers
+                .iter()
+                .filter_map(|file| Listening::new("numbers", file))
+                .map(|exercise| Box::new(exercise) as Box<dyn Exercise>)
+                .collect(),
+            self.letters
+                .iter()
+                .filter_map(|file| Listening::new("alphabet", file))
+                .map(|exercise| Box::new(exercise) as Box<dyn Exercise>)
+                .collect(),
+        ]
+    }
+}
+
+/// The audio files in the directory whose name is accepted by `include`, or none if there is no
+/// such directory
+fn audio_files(directory: &Path, include: impl Fn(&str) -> bool) -> Result<Vec<PathBuf>> {
+    if !directory.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = list_audio_files_in_directory(directory)?;
+    files.retain(|file| {
+        file.file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(&include)
+    });
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::config::Config;
+    use crate::types::data::DataDir;
+    use crate::types::error::Result;
+    use crate::types::exercise::{attempt, Exercise, Outcome};
+    use crate::types::nouns::{ArticleExercise, Noun, PluralExercise};
+    use crate::types::ui::{Input, Message, Ui};
+    use crate::types::verbs::{Conjugation, Pronoun, Tense, Verb};
+
+    /// Answers in turn with the given inputs, and keeps what is shown
+    struct Scripted {
+        inputs: Vec<Input>,
+        shown: Vec<String>,
+    }
+
+    impl Ui for Scripted {
+        fn start(&mut self, _title: &str, _items: usize) {}
+
+        fn ask(&mut self, _prompt: &Message, _remaining: usize) -> Result<Input> {
+            Ok(self.inputs.remove(0))
+        }
+
+        fn show(&mut self, message: Message) {
+            self.shown.push(message.plain_text());
+        }
+
+        fn score(&mut self, _correct: bool) {}
+
+        fn help(&mut self, expected: &str) {
+            self.shown.push(format!("Expected {}", expected));
+        }
+
+        fn finish(&mut self, _summary: Vec<Message>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct Capital;
+
+    impl Exercise for Capital {
+        fn mode(&self) -> &'static str {
+            "test"
+        }
+
+        fn item(&self) -> String {
+            "Deutschland".to_string()
+        }
+
+        fn variant(&self) -> String {
+            "capital".to_string()
+        }
+
+        fn present(&self, _data: &DataDir) -> Message {
+            "The capital of Germany".into()
+        }
+
+        fn expected(&self) -> &'static str {
+            "a city"
+        }
+
+        fn check(&self, _config: &Config, answer: &str) -> Option<bool> {
+            (!answer.contains(' ')).then(|| answer == "Berlin")
+        }
+
+        fn feedback(
+            &self,
+            _data: &DataDir,
+            _config: &Config,
+            ui: &mut dyn Ui,
+            _answer: &str,
+            correct: bool,
+        ) {
+            ui.show(format!("{} Berlin", if correct { "Yes," } else { "No," }).into());
+        }
+
+        fn replay(&self, _data: &DataDir, _slow: bool) -> bool {
+            false
+        }
+
+        fn english(&self) -> Option<String> {
+            None
+        }
+
+        fn solution(&self) -> String {
+            "Berlin".to_string()
+        }
+    }
+
+    #[test]
+    fn attempt_until_answered() {
+        /* The answers are recorded in the history of a scratch directory */
+        let dir = std::env::temp_dir().join("practice-deutsch-attempt");
+        let data = DataDir::new(&dir);
+        let mut ui = Scripted {
+            inputs: vec![
+                Input::Replay,
+                Input::Answer("Bad Godesberg".to_string()),
+                Input::Answer("Bonn".to_string()),
+            ],
+            shown: vec![],
+        };
+
+        let outcome = attempt(&Capital, &data, &Config::default(), &mut ui, 1).unwrap();
+        assert_eq!(outcome, Outcome::Wrong);
+        assert_eq!(
+            ui.shown,
+            vec!["Expected a city", "Expected a city", "No, Berlin"]
+        );
+
+        ui.inputs = vec![Input::Skip];
+        let outcome = attempt(&Capital, &data, &Config::default(), &mut ui, 1).unwrap();
+        assert_eq!(outcome, Outcome::Skipped);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn check_answers() {
+        let config = Config::default();
+        let apfel = Noun::example("apple", "der", "Apfel", "Äpfel");
+        let csv = "english,german,ich,du,er,wir,ihr,sie,prefix,reflexive\n\
+                   to get dressed,anziehen,ziehe,ziehst,zieht,ziehen,zieht,ziehen,an,true\n";
+        let anziehen: Verb = csv::Reader::from_reader(csv.as_bytes())
+            .deserialize()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(ArticleExercise(&apfel).check(&config, "Der"), Some(true));
+        assert_eq!(ArticleExercise(&apfel).check(&config, "den"), None);
+        assert_eq!(PluralExercise(&apfel).check(&config, "Apfel"), Some(false));
+
+        let conjugation = Conjugation::standalone(&anziehen, &Tense::Praesens, &Pronoun::Ich);
+        assert_eq!(conjugation.check(&config, "ziehe mich an"), Some(true));
+        assert_eq!(conjugation.check(&config, "an ziehe mich"), Some(false));
+        assert_eq!(conjugation.variant(), "praesens ich");
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt::{Display, Formatter};
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::nouns::Noun;
+
+/// A rule of thumb that predicts the article of a noun from its ending.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct GenderRule {
+    pub(crate) suffix: &'static str,
+    pub(crate) article: &'static str,
+    /// Endings that look like the suffix without being it, such as -kuchen for -chen
+    not_after: &'static [&'static str],
+}
+
+/// The outcome of checking a noun against the gender rules.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RuleCheck {
+    /// The noun has the article predicted by its ending
+    Follows(&'static GenderRule),
+    /// The noun has an ending with a rule, but a different article
+    Exception(&'static GenderRule),
+    /// No rule covers the ending of this noun
+    NoRule,
+}
+
+/* Compound nouns take the gender of their last part, so matching on the ending also works for
+words like "Zeitung" or "Mädchen".  Longer suffixes come first so that the most specific rule
+wins.  Only the Latin -ium and -eum count for -um, as Baum or Raum are der. */
+static RULES: [GenderRule; 18] = [
+    GenderRule::new("ismus", "der"),
+    GenderRule::new("schaft", "die"),
+    GenderRule::new("heit", "die"),
+    GenderRule::new("keit", "die"),
+    GenderRule::new("chen", "das").not_after(&["kuchen", "knochen", "rachen", "drachen"]),
+    GenderRule::new("lein", "das"),
+    GenderRule::new("ment", "das"),
+    GenderRule::new("ling", "der"),
+    GenderRule::new("ung", "die"),
+    GenderRule::new("ion", "die"),
+    GenderRule::new("tät", "die"),
+    GenderRule::new("enz", "die"),
+    GenderRule::new("anz", "die"),
+    GenderRule::new("tum", "das"),
+    GenderRule::new("ium", "das"),
+    GenderRule::new("eum", "das"),
+    GenderRule::new("ik", "die"),
+    GenderRule::new("ei", "die"),
+];
+
+impl GenderRule {
+    const fn new(suffix: &'static str, article: &'static str) -> Self {
+        GenderRule {
+            suffix,
+            article,
+            not_after: &[],
+        }
+    }
+
+    const fn not_after(self, endings: &'static [&'static str]) -> Self {
+        GenderRule {
+            not_after: endings,
+            ..self
+        }
+    }
+
+    pub(crate) fn find(singular: &str) -> Option<&'static GenderRule> {
+        let singular = singular.to_lowercase();
+        RULES.iter().find(|rule| {
+            singular.len() > rule.suffix.len()
+                && singular.ends_with(rule.suffix)
+                && !rule
+                    .not_after
+                    .iter()
+                    .any(|ending| singular.ends_with(ending))
+        })
+    }
+
+    pub(crate) fn check(noun: &Noun) -> RuleCheck {
+        match GenderRule::find(&noun.singular) {
+            Some(rule) if rule.article == noun.article => RuleCheck::Follows(rule),
+            Some(rule) => RuleCheck::Exception(rule),
+            None => RuleCheck::NoRule,
+        }
+    }
+}
+
+impl Display for GenderRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "nouns ending in -{} are {}", self.suffix, self.article)
+    }
+}
+
+/// Explains why the noun has its article, if a rule covers it
+pub(crate) fn hint(noun: &Noun) -> Option<String> {
+    match GenderRule::check(noun) {
+        RuleCheck::Follows(rule) => Some(format!("Rule: {}", rule)),
+        RuleCheck::Exception(rule) => Some(format!(
+            "Exception: {} is {} although {}",
+            noun.singular, noun.article, rule
+        )),
+        RuleCheck::NoRule => None,
+    }
+}
+
+pub(crate) fn audit(data: &DataDir, config: &Config) -> Result<()> {
+    let nouns = Selection::new(data, config)?.nouns(data)?;
+
+    let covered = nouns
+        .iter()
+        .filter(|noun| GenderRule::check(noun) != RuleCheck::NoRule)
+        .count();
+    let exceptions = exceptions(&nouns);
+
+    println!("------------------------------------------------------------");
+    println!(
+        "{} of {} nouns are covered by a gender rule",
+        covered,
+        nouns.len()
+    );
+    println!("{} nouns break their rule", exceptions.len());
+    println!("------------------------------------------------------------");
+    for rule in RULES.iter() {
+        let breaking: Vec<_> = exceptions.iter().filter(|(_, r)| *r == rule).collect();
+        if breaking.is_empty() {
+            continue;
+        }
+
+        println!("-{} ({})", rule.suffix, rule.article);
+        breaking
+            .iter()
+            .for_each(|(noun, _)| println!(" - {}", noun));
+    }
+    println!("------------------------------------------------------------");
+
+    Ok(())
+}
+
+/// The nouns that break the rule of their ending, with that rule
+fn exceptions(nouns: &[Noun]) -> Vec<(&Noun, &'static GenderRule)> {
+    nouns
+        .iter()
+        .filter_map(|noun| match GenderRule::check(noun) {
+            RuleCheck::Exception(rule) => Some((noun, rule)),
+            RuleCheck::Follows(_) | RuleCheck::NoRule => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::gender::{exceptions, GenderRule, RuleCheck};
+    use crate::types::nouns::Noun;
+
+    fn noun(article: &str, singular: &str) -> Noun {
+        Noun {
+            tags: None,
+            level: None,
+            english: String::new(),
+            article: article.to_string(),
+            singular: singular.to_string(),
+            plural: None,
+        }
+    }
+
+    #[test]
+    fn follows_rule() {
+        let check = GenderRule::check(&noun("die", "Zeitung"));
+        assert!(matches!(check, RuleCheck::Follows(rule) if rule.suffix == "ung"));
+    }
+
+    #[test]
+    fn exception_to_rule() {
+        let check = GenderRule::check(&noun("der", "Reichtum"));
+        assert!(matches!(check, RuleCheck::Exception(rule) if rule.article == "das"));
+    }
+
+    #[test]
+    fn suffix_must_be_shorter_than_noun() {
+        assert_eq!(GenderRule::check(&noun("das", "Ei")), RuleCheck::NoRule);
+    }
+
+    #[test]
+    fn endings_that_only_look_like_a_suffix() {
+        let nouns = vec![
+            noun("der", "Baum"),
+            noun("der", "Raum"),
+            noun("der", "Kuchen"),
+            noun("der", "Erdbeerkuchen"),
+            noun("das", "Museum"),
+            noun("das", "Brötchen"),
+        ];
+        assert!(exceptions(&nouns).is_empty());
+        assert_eq!(GenderRule::check(&noun("der", "Kuchen")), RuleCheck::NoRule);
+        assert!(matches!(
+            GenderRule::check(&noun("das", "Museum")),
+            RuleCheck::Follows(rule) if rule.suffix == "eum"
+        ));
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use csv::StringRecord;
+
+use crate::types::data::DataDir;
+use crate::types::error::{Error, Result};
+use crate::types::nouns::NOUNS_FILE;
+use crate::types::phrases::PHRASES_FILE;
+use crate::types::tags::Level;
+use crate::types::verbs::VERBS_FILE;
+
+const NOUN_COLUMNS: [&str; 4] = ["english", "article", "singular", "plural"];
+const VERB_COLUMNS: [&str; 8] = ["english", "german", "ich", "du", "er", "wir", "ihr", "sie"];
+const PRAETERITUM_COLUMNS: [&str; 6] = [
+    "praeteritum_ich",
+    "praeteritum_du",
+    "praeteritum_er",
+    "praeteritum_wir",
+    "praeteritum_ihr",
+    "praeteritum_sie",
+];
+const PHRASE_COLUMNS: [&str; 2] = ["english", "german"];
+
+#[derive(Debug, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in one of the CSV files
+#[derive(Debug)]
+struct Diagnostic {
+    file: &'static str,
+    line: u64,
+    severity: Severity,
+    message: String,
+    /// Whether the problem was corrected in the file (with --fix)
+    fixed: bool,
+}
+
+/// The rows of a CSV file, with the line number of each
+struct Table {
+    file: &'static str,
+    path: PathBuf,
+    headers: StringRecord,
+    rows: Vec<(u64, StringRecord)>,
+    diagnostics: Vec<Diagnostic>,
+    changed: bool,
+}
+
+/// Checks the CSV files and prints a file:line diagnostic for each problem.  Safe corrections
+/// (such as trimming whitespace) are written back to the files when `fix` is set.  Fails if any
+/// errors remain.
+pub(crate) fn lint(data: &DataDir, fix: bool) -> Result<()> {
+    let mut diagnostics = Vec::new();
+    for (file, lint_rows) in [
+        (NOUNS_FILE, lint_nouns as fn(&mut Table, bool)),
+        (VERBS_FILE, lint_verbs),
+        (PHRASES_FILE, lint_phrases),
+    ] {
+        diagnostics.append(&mut lint_file(data, file, fix, lint_rows));
+    }
+
+    for diagnostic in diagnostics.iter() {
+        println!("{}", diagnostic);
+    }
+
+    let remaining = |severity: Severity| {
+        diagnostics
+            .iter()
+            .filter(|d| !d.fixed && d.severity == severity)
+            .count()
+    };
+    let errors = remaining(Severity::Error);
+    println!("------------------------------------------------------------");
+    println!(
+        "{} errors, {} warnings, {} fixed",
+        errors,
+        remaining(Severity::Warning),
+        diagnostics.iter().filter(|d| d.fixed).count()
+    );
+    println!("------------------------------------------------------------");
+
+    if errors > 0 {
+        return Err(Error::Lint(errors));
+    }
+    Ok(())
+}
+
+fn lint_file(
+    data: &DataDir,
+    file: &'static str,
+    fix: bool,
+    lint_rows: fn(&mut Table, bool),
+) -> Vec<Diagnostic> {
+    let mut table = match Table::read(file, &data.file(file)) {
+        Ok(table) => table,
+        Err(e) => {
+            return vec![Diagnostic::error(
+                file,
+                e.position().map(|p| p.line()).unwrap_or(0),
+                e.to_string(),
+            )]
+        }
+    };
+
+    table.check_trimmed(fix);
+    lint_rows(&mut table, fix);
+    table.check_level(fix);
+
+    if table.changed {
+        if let Err(e) = table.write() {
+            table.diagnostics.push(Diagnostic::error(
+                file,
+                0,
+                format!("failed to save fixes ({})", e),
+            ));
+        }
+    }
+    table.diagnostics
+}
+
+fn lint_nouns(table: &mut Table, fix: bool) {
+    if !table.has_columns(&NOUN_COLUMNS) {
+        return;
+    }
+
+    let mut singulars: HashMap<String, (u64, String)> = HashMap::new();
+    for index in 0..table.rows.len() {
+        table.check_not_empty(index, &["english", "article", "singular"]);
+
+        let article = table.field(index, "article");
+        if !is_article(&article) {
+            if fix && is_article(&article.to_lowercase()) {
+                table.set_field(index, "article", &article.to_lowercase());
+                table.fixed(index, format!("article '{}' is not lowercase", article));
+            } else {
+                table.error(index, format!("unknown article '{}'", article));
+            }
+        }
+
+        for column in ["singular", "plural"] {
+            let noun = table.field(index, column);
+            if !noun.is_empty() && !starts_with_uppercase(&noun) {
+                let message = format!("{} '{}' is not capitalised", column, noun);
+                if fix {
+                    table.set_field(index, column, &capitalise(&noun));
+                    table.fixed(index, message);
+                } else {
+                    table.error(index, message);
+                }
+            }
+        }
+
+        let singular = table.field(index, "singular");
+        let plural = table.field(index, "plural");
+        if !plural.is_empty() && !is_plausible_plural(&singular, &plural) {
+            table.warning(
+                index,
+                format!(
+                    "plural '{}' does not look like a plural of '{}'",
+                    plural, singular
+                ),
+            );
+        }
+
+        let article = table.field(index, "article");
+        let line = table.rows[index].0;
+        match singulars.get(&singular) {
+            /* Some nouns have a different meaning with each article (der/die Flur) */
+            Some((first, first_article)) if *first_article != article => table.warning(
+                index,
+                format!(
+                    "singular '{}' is also listed on line {} with the article {}",
+                    singular, first, first_article
+                ),
+            ),
+            Some((first, _)) => table.error(
+                index,
+                format!("duplicate singular '{}' (see line {})", singular, first),
+            ),
+            None => {
+                singulars.insert(singular, (line, article));
+            }
+        }
+    }
+}
+
+fn lint_verbs(table: &mut Table, _fix: bool) {
+    if !table.has_columns(&VERB_COLUMNS) {
+        return;
+    }
+
+    let mut infinitives: HashMap<String, u64> = HashMap::new();
+    for index in 0..table.rows.len() {
+        table.check_not_empty(index, &VERB_COLUMNS);
+
+        /* The Präteritum is optional, but then all forms are needed */
+        let praeteritum: Vec<String> = PRAETERITUM_COLUMNS
+            .iter()
+            .map(|column| table.field(index, column))
+            .collect();
+        if praeteritum.iter().any(|form| !form.is_empty())
+            && praeteritum.iter().any(|form| form.is_empty())
+        {
+            table.error(
+                index,
+                "some of the praeteritum columns are empty".to_string(),
+            );
+        }
+
+        let auxiliary = table.field(index, "auxiliary");
+        if !["", "haben", "sein"].contains(&auxiliary.as_str()) {
+            table.error(
+                index,
+                format!("auxiliary '{}' is neither haben nor sein", auxiliary),
+            );
+        }
+        if auxiliary.is_empty() != table.field(index, "partizip").is_empty() {
+            table.error(index, "auxiliary and partizip go together".to_string());
+        }
+
+        let reflexive = table.field(index, "reflexive");
+        if !["", "true", "false"].contains(&reflexive.as_str()) {
+            table.error(
+                index,
+                format!("reflexive '{}' is neither true nor false", reflexive),
+            );
+        }
+
+        let infinitive = table.field(index, "german");
+        if let Some(first) = infinitives.get(&infinitive) {
+            table.error(
+                index,
+                format!("duplicate verb '{}' (see line {})", infinitive, first),
+            );
+        } else {
+            infinitives.insert(infinitive, table.rows[index].0);
+        }
+    }
+}
+
+fn lint_phrases(table: &mut Table, _fix: bool) {
+    if !table.has_columns(&PHRASE_COLUMNS) {
+        return;
+    }
+
+    let mut phrases: HashMap<String, u64> = HashMap::new();
+    for index in 0..table.rows.len() {
+        table.check_not_empty(index, &PHRASE_COLUMNS);
+
+        let german = table.field(index, "german");
+        if let Some(first) = phrases.get(&german) {
+            table.error(
+                index,
+                format!("duplicate phrase '{}' (see line {})", german, first),
+            );
+        } else {
+            phrases.insert(german, table.rows[index].0);
+        }
+    }
+}
+
+impl Table {
+    fn read(file: &'static str, path: &Path) -> std::result::Result<Self, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::None)
+            .from_path(path)?;
+        let headers = reader.headers()?.clone();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map(|p| p.line()).unwrap_or(0);
+            rows.push((line, record));
+        }
+
+        Ok(Table {
+            file,
+            path: path.to_path_buf(),
+            headers,
+            rows,
+            diagnostics: vec![],
+            changed: false,
+        })
+    }
+
+    fn write(&self) -> std::result::Result<(), csv::Error> {
+        let mut writer = csv::Writer::from_path(&self.path)?;
+        writer.write_record(&self.headers)?;
+        for (_, record) in self.rows.iter() {
+            writer.write_record(record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn has_columns(&mut self, columns: &[&str]) -> bool {
+        let missing: Vec<&&str> = columns
+            .iter()
+            .filter(|column| !self.headers.iter().any(|header| header == **column))
+            .collect();
+        for column in missing.iter() {
+            self.diagnostics.push(Diagnostic::error(
+                self.file,
+                1,
+                format!("missing column '{}'", column),
+            ));
+        }
+        missing.is_empty()
+    }
+
+    /// The value of the column, or an empty string if the file does not have this column
+    fn field(&self, index: usize, column: &str) -> String {
+        self.headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|position| self.rows[index].1.get(position))
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn set_field(&mut self, index: usize, column: &str, value: &str) {
+        let Some(position) = self.headers.iter().position(|header| header == column) else {
+            return;
+        };
+        let record: StringRecord = self.rows[index]
+            .1
+            .iter()
+            .enumerate()
+            .map(|(i, field)| if i == position { value } else { field })
+            .collect();
+        self.rows[index].1 = record;
+        self.changed = true;
+    }
+
+    /// The optional level column has one of the CEFR levels, such as A1
+    fn check_level(&mut self, fix: bool) {
+        for index in 0..self.rows.len() {
+            let level = self.field(index, "level");
+            if level.is_empty() || is_level(&level) {
+                continue;
+            }
+
+            if fix && is_level(&level.to_uppercase()) {
+                self.set_field(index, "level", &level.to_uppercase());
+                self.fixed(index, format!("level '{}' is not uppercase", level));
+            } else {
+                self.error(index, format!("unknown level '{}'", level));
+            }
+        }
+    }
+
+    fn check_trimmed(&mut self, fix: bool) {
+        let headers: Vec<String> = self.headers.iter().map(String::from).collect();
+        for index in 0..self.rows.len() {
+            for header in headers.iter() {
+                let value = self.field(index, header);
+                if value.trim() == value {
+                    continue;
+                }
+
+                let message = format!("{} '{}' has leading or trailing whitespace", header, value);
+                if fix {
+                    self.set_field(index, header, value.trim());
+                    self.fixed(index, message);
+                } else {
+                    self.error(index, message);
+                }
+            }
+        }
+    }
+
+    fn check_not_empty(&mut self, index: usize, columns: &[&str]) {
+        for column in columns {
+            if self.field(index, column).is_empty() {
+                self.error(index, format!("{} is empty", column));
+            }
+        }
+    }
+
+    fn error(&mut self, index: usize, message: String) {
+        let line = self.rows[index].0;
+        self.diagnostics
+            .push(Diagnostic::error(self.file, line, message));
+    }
+
+    fn warning(&mut self, index: usize, message: String) {
+        let mut diagnostic = Diagnostic::error(self.file, self.rows[index].0, message);
+        diagnostic.severity = Severity::Warning;
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn fixed(&mut self, index: usize, message: String) {
+        let mut diagnostic = Diagnostic::error(self.file, self.rows[index].0, message);
+        diagnostic.fixed = true;
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Diagnostic {
+    fn error(file: &'static str, line: u64, message: String) -> Self {
+        Diagnostic {
+            file,
+            line,
+            severity: Severity::Error,
+            message,
+            fixed: false,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = match (&self.severity, self.fixed) {
+            (_, true) => "fixed",
+            (Severity::Error, _) => "error",
+            (Severity::Warning, _) => "warning",
+        };
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.file, self.line, label, self.message
+        )
+    }
+}
+
+fn is_level(level: &str) -> bool {
+    Level::from_str(level, false).is_ok()
+}
+
+pub(crate) fn is_article(article: &str) -> bool {
+    matches!(article, "der" | "die" | "das")
+}
+
+pub(crate) fn starts_with_uppercase(noun: &str) -> bool {
+    noun.chars().next().is_some_and(char::is_uppercase)
+}
+
+fn capitalise(noun: &str) -> String {
+    let mut chars = noun.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Plurals keep the stem of the singular (allowing for an umlaut, as in Apfel and Äpfel) and
+/// lose at most a couple of letters (as in Museum and Museen)
+fn is_plausible_plural(singular: &str, plural: &str) -> bool {
+    fn stem(noun: &str) -> Vec<char> {
+        noun.to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'ä' => 'a',
+                'ö' => 'o',
+                'ü' => 'u',
+                c => c,
+            })
+            .collect()
+    }
+
+    let singular = stem(singular);
+    let plural = stem(plural);
+    let common = singular
+        .iter()
+        .zip(plural.iter())
+        .take_while(|(s, p)| s == p)
+        .count();
+
+    common >= 3.min(singular.len().saturating_sub(1)) && plural.len() + 2 >= singular.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::lint::is_plausible_plural;
+
+    #[test]
+    fn plausible_plurals() {
+        assert!(is_plausible_plural("Apfel", "Äpfel"));
+        assert!(is_plausible_plural("Museum", "Museen"));
+        assert!(is_plausible_plural("Ei", "Eier"));
+        assert!(!is_plausible_plural("Haus", "Autos"));
+        assert!(!is_plausible_plural("Praktikum", "Prak"));
+    }
+}
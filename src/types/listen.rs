@@ -0,0 +1,282 @@
+use std::path::PathBuf;
+
+use rand::prelude::{IteratorRandom, SliceRandom};
+use rand::thread_rng;
+
+use crate::types::audio::{
+    play_file_or_print_error, play_file_slowly_or_print_error, playback_enabled,
+};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::phrases::Phrases;
+use crate::types::ui::{Message, Ui};
+
+/* The words left out before comparing the meanings, as in an apple or to call */
+const FILLER_WORDS: [&str; 4] = ["a", "an", "the", "to"];
+
+/// The English meaning of a noun or a phrase that is only heard
+#[derive(Clone)]
+struct Meaning<'a> {
+    /// The noun or the phrase, as recorded in the history
+    item: &'a str,
+    /// What is heard, such as der Apfel
+    german: String,
+    english: &'a str,
+    audio: PathBuf,
+    /// Either noun or phrase
+    kind: &'static str,
+    /// The meanings of other nouns or phrases, to pick from in the multiple choice
+    others: Vec<&'a str>,
+}
+
+/// Plays nouns and phrases without showing them, and asks for their English meaning
+pub(crate) fn listen(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    if !playback_enabled() {
+        return ui.finish(vec!["This mode needs the audio, which is turned off".into()]);
+    }
+
+    let selection = Selection::new(data, config)?;
+    let nouns = selection.nouns(data)?;
+    let phrases = Phrases::read(data)?;
+    let phrases = selection.phrases(&phrases);
+
+    /* Only what has a recording can be asked, as the text is not shown */
+    let meanings: Vec<Meaning> = nouns
+        .iter()
+        .map(|noun| Meaning {
+            item: &noun.singular,
+            german: format!("{} {}", noun.article, noun.singular),
+            english: &noun.english,
+            audio: noun.singular_with_article_file_path(data),
+            kind: "noun",
+            others: vec![],
+        })
+        .chain(phrases.iter().map(|phrase| Meaning {
+            item: &phrase.german,
+            german: phrase.german.clone(),
+            english: &phrase.english,
+            audio: phrase.audio_file_path(data),
+            kind: "phrase",
+            others: vec![],
+        }))
+        .filter(|meaning| meaning.audio.is_file())
+        .collect();
+    if meanings.is_empty() {
+        return ui.finish(vec!["No recorded nouns or phrases found".into()]);
+    }
+
+    let mut rng = thread_rng();
+    let mut picked: Vec<Meaning> = meanings
+        .choose_multiple(&mut rng, config.articles.count)
+        .cloned()
+        .collect();
+    for meaning in picked.iter_mut() {
+        meaning.others = meanings
+            .iter()
+            .filter(|other| other.kind == meaning.kind && other.english != meaning.english)
+            .map(|other| other.english)
+            .choose_multiple(&mut rng, 4);
+    }
+    picked.shuffle(&mut rng);
+
+    let number_of_items = picked.len();
+    let mut incorrect: Vec<String> = Vec::new();
+
+    ui.start("recordings", number_of_items);
+
+    while !picked.is_empty() {
+        let meaning = picked.remove(0);
+        match attempt(&meaning, data, config, ui, picked.len() + 1)? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Wrong => {
+                let line = format!(" - {}: {}", meaning.german, meaning.english);
+                if !incorrect.contains(&line) {
+                    incorrect.push(line);
+                }
+                picked.push(meaning);
+            }
+            Outcome::Correct | Outcome::Skipped => {}
+        }
+    }
+
+    let mut summary = vec![Message::new(format!(
+        "Finished {} recordings with {} incorrect answers",
+        number_of_items,
+        incorrect.len()
+    ))];
+    summary.extend(incorrect.into_iter().map(Message::new));
+    ui.finish(summary)
+}
+
+impl Exercise for Meaning<'_> {
+    fn mode(&self) -> &'static str {
+        "listen"
+    }
+
+    fn item(&self) -> String {
+        self.item.to_string()
+    }
+
+    fn variant(&self) -> String {
+        self.kind.to_string()
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        play_file_or_print_error(&self.audio);
+        "What does it mean".into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the English meaning"
+    }
+
+    fn check(&self, _config: &Config, answer: &str) -> Option<bool> {
+        Some(means(self.english, answer))
+    }
+
+    fn feedback(
+        &self,
+        _data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let verdict = if correct { "Correct" } else { "Wrong! It was" };
+        ui.show(format!("{}: {} ({})", verdict, self.german, self.english).into());
+    }
+
+    fn replay(&self, _data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.audio);
+        } else {
+            play_file_or_print_error(&self.audio);
+        }
+        true
+    }
+
+    /* The translation is the answer */
+    fn english(&self) -> Option<String> {
+        None
+    }
+
+    fn solution(&self) -> String {
+        self.english.to_string()
+    }
+
+    fn choices(&self) -> Vec<String> {
+        let mut choices = vec![self.english.to_string()];
+        choices.extend(self.others.iter().map(|other| other.to_string()));
+        choices
+    }
+}
+
+/// Whether the answer gives the meaning of the English gloss, such as "hairdresser or barber" or
+/// "vixen (a female fox)": the whole gloss, one of its alternatives or its hint in parentheses.
+/// The case, the punctuation, a leading a, the or to and small typos are ignored.
+fn means(english: &str, answer: &str) -> bool {
+    let answer = normalise(answer);
+    if answer.is_empty() {
+        return false;
+    }
+
+    meanings(english).iter().any(|meaning| {
+        let allowed = meaning.chars().count() / 6;
+        edit_distance(meaning, &answer) <= allowed
+    })
+}
+
+/// The ways the gloss can be answered, normalised
+fn meanings(english: &str) -> Vec<String> {
+    let mut gloss = String::new();
+    let mut hints: Vec<String> = Vec::new();
+    let mut depth = 0;
+    for c in english.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                if depth == 1 {
+                    hints.push(String::new());
+                }
+            }
+            ')' => depth -= 1,
+            c if depth > 0 => hints.last_mut().unwrap().push(c),
+            c => gloss.push(c),
+        }
+    }
+
+    let mut meanings = vec![normalise(&gloss)];
+    for text in std::iter::once(gloss.as_str()).chain(hints.iter().map(String::as_str)) {
+        for alternative in text
+            .split([',', ';', '/'])
+            .flat_map(|part| part.split(" or "))
+        {
+            let alternative = normalise(alternative);
+            /* Hints such as (F) only tell the gender */
+            if alternative.chars().count() > 1 && !meanings.contains(&alternative) {
+                meanings.push(alternative);
+            }
+        }
+    }
+    meanings.retain(|meaning| !meaning.is_empty());
+    meanings
+}
+
+/// Lower case words without the punctuation and the leading filler word
+fn normalise(text: &str) -> String {
+    let text: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() > 1 && FILLER_WORDS.contains(&words[0]) {
+        words.remove(0);
+    }
+    words.join(" ")
+}
+
+/// The number of characters to insert, remove or replace to turn one into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous[j] + (ca != *cb) as usize;
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::listen::{edit_distance, means};
+
+    #[test]
+    fn fuzzy_meanings() {
+        assert!(means("hairdresser or barber", "Barber"));
+        assert!(means("hairdresser or barber", "hairdreser"));
+        assert!(means("vixen (a female fox)", "female fox"));
+        assert!(means("architect (F)", "an architect"));
+        assert!(!means("architect (F)", "f"));
+        assert!(means("to call (on the phone)", "call"));
+        assert!(means("Does Tom need a lawyer?", "does tom need a lawyer"));
+        assert!(!means("apple", "apricot"));
+        assert!(!means("day", "dad"));
+
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
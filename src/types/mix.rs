@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::Args as ClapArgs;
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome, Pool, MIXED};
+use crate::types::progress::{Attempt, History};
+use crate::types::ui::{Message, Ui};
+
+/* The weight of an item that was never practised, between a learned and a forgotten one */
+const NEW_ITEM: f64 = 2.0;
+
+/* After this long without practice, an item counts as fully due */
+const DUE_AFTER_DAYS: f64 = 7.0;
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct MixArgs {
+    /// The number of items, shared between the drills
+    #[clap(long, default_value_t = 25)]
+    pub(crate) count: usize,
+}
+
+/// What the history says about one item of a drill
+#[derive(Debug, Default)]
+struct Record {
+    answers: usize,
+    wrong: usize,
+    last_correct: bool,
+    /// Seconds since the Unix epoch
+    last_time: u64,
+}
+
+/// Interleaves the articles, plurals, verbs, numbers and letters, picking the items that are new,
+/// often wrong or not practised for a while more often.  A wrong answer is asked again later.
+pub(crate) fn mix(
+    data: &DataDir,
+    config: &Config,
+    options: &MixArgs,
+    ui: &mut dyn Ui,
+) -> Result<()> {
+    let pool = Pool::new(data, config)?;
+    let history = History::read(data)?;
+    let picked = pick(pool.exercises(), &records(&history), options.count);
+    if picked.is_empty() {
+        return ui.finish(vec!["No items found to mix".into()]);
+    }
+
+    ui.start("mixed items", picked.len());
+
+    /* Each exercise with whether it is asked again */
+    let mut exercises: Vec<(Box<dyn Exercise + '_>, bool)> = picked
+        .into_iter()
+        .map(|exercise| (exercise, false))
+        .collect();
+    let mut scores: Vec<(&'static str, usize, usize)> = Vec::new();
+    while !exercises.is_empty() {
+        let (exercise, again) = exercises.remove(0);
+        let outcome = attempt(exercise.as_ref(), data, config, ui, exercises.len() + 1)?;
+        if outcome == Outcome::Quit {
+            break;
+        }
+
+        /* Only the first answer counts towards the score */
+        if !again && outcome != Outcome::Skipped {
+            let index = match scores
+                .iter()
+                .position(|(mode, _, _)| *mode == exercise.mode())
+            {
+                Some(index) => index,
+                None => {
+                    scores.push((exercise.mode(), 0, 0));
+                    scores.len() - 1
+                }
+            };
+            scores[index].1 += (outcome == Outcome::Correct) as usize;
+            scores[index].2 += 1;
+        }
+
+        /* A wrong answer is asked once more, at the end */
+        if outcome == Outcome::Wrong && !again {
+            exercises.push((exercise, true));
+        }
+    }
+
+    scores.sort_by_key(|(mode, _, _)| MIXED.iter().position(|name| name == mode));
+    let answered: usize = scores.iter().map(|(_, _, total)| total).sum();
+    let mut summary = vec![Message::new(format!("Finished {} mixed items", answered))];
+    summary.extend(scores.iter().map(|(mode, correct, total)| {
+        Message::new(format!(
+            "{:<10} {:>2} of {:>2} correct",
+            mode, correct, total
+        ))
+    }));
+    ui.finish(summary)
+}
+
+/// The answers of each item, by mode and item
+fn records(history: &History) -> HashMap<(&str, &str), Record> {
+    let mut records: HashMap<(&str, &str), Record> = HashMap::new();
+    for Attempt {
+        timestamp,
+        mode,
+        item,
+        correct,
+        ..
+    } in history.attempts()
+    {
+        let record = records.entry((mode, item)).or_default();
+        record.answers += 1;
+        record.wrong += !correct as usize;
+        record.last_correct = *correct;
+        record.last_time = *timestamp;
+    }
+    records
+}
+
+/// How much the item needs practice: the share of wrong answers, whether the last one was wrong,
+/// and how long ago it was practised
+fn weight(record: Option<&Record>, now: u64) -> f64 {
+    let Some(record) = record else {
+        return NEW_ITEM;
+    };
+
+    let wrong = record.wrong as f64 / record.answers as f64;
+    let forgotten = if record.last_correct { 0.0 } else { 2.0 };
+    let days = now.saturating_sub(record.last_time) as f64 / (24.0 * 60.0 * 60.0);
+    1.0 + 2.0 * wrong + forgotten + days.min(DUE_AFTER_DAYS) / DUE_AFTER_DAYS
+}
+
+/// The same share of items from each drill that has any, picked by their weight, in random order
+fn pick<'a>(
+    drills: Vec<Vec<Box<dyn Exercise + 'a>>>,
+    records: &HashMap<(&str, &str), Record>,
+    count: usize,
+) -> Vec<Box<dyn Exercise + 'a>> {
+    let mut rng = thread_rng();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let mut drills: Vec<Vec<Box<dyn Exercise + 'a>>> = drills
+        .into_iter()
+        .filter(|exercises| !exercises.is_empty())
+        .collect();
+    let mut picked = Vec::new();
+    while picked.len() < count && !drills.is_empty() {
+        let share = ((count - picked.len()) / drills.len()).max(1);
+        for exercises in drills.iter_mut() {
+            let weights: Vec<f64> = exercises
+                .iter()
+                .map(|exercise| {
+                    let item = exercise.item();
+                    weight(records.get(&(exercise.mode(), item.as_str())), now)
+                })
+                .collect();
+            let mut indices: Vec<usize> = (0..exercises.len()).collect();
+            let share = share.min(count - picked.len()).min(exercises.len());
+            let chosen: Vec<usize> = indices
+                .choose_multiple_weighted(&mut rng, share, |index| weights[*index])
+                .map(|chosen| chosen.copied().collect())
+                .unwrap_or_default();
+            indices.retain(|index| chosen.contains(index));
+
+            /* Taken from the back, so that the indices left stay valid */
+            for index in indices.into_iter().rev() {
+                picked.push(exercises.remove(index));
+            }
+        }
+        drills.retain(|exercises| !exercises.is_empty());
+    }
+
+    picked.shuffle(&mut rng);
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::mix::{weight, Record, NEW_ITEM};
+
+    #[test]
+    fn weak_and_due_items_first() {
+        let day = 24 * 60 * 60;
+        let now = 30 * day;
+        let record = |answers, wrong, last_correct, days_ago: u64| Record {
+            answers,
+            wrong,
+            last_correct,
+            last_time: now - days_ago * day,
+        };
+
+        let learned = weight(Some(&record(4, 0, true, 0)), now);
+        let forgotten = weight(Some(&record(4, 1, false, 0)), now);
+        let due = weight(Some(&record(4, 0, true, 10)), now);
+        assert_eq!(learned, 1.0);
+        assert_eq!(forgotten, 3.5);
+        assert_eq!(due, 2.0);
+        assert_eq!(weight(None, now), NEW_ITEM);
+    }
+}
@@ -1,90 +1,51 @@
-use colored::{ColoredString, Colorize};
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
-use std::path::{Path, PathBuf};
-
-use crate::types::audio::play_file_or_print_error;
-use crate::types::utils::{play_and_read_line, read_line, remove_random};
-
-pub(crate) fn articles() {
-    let mut nouns: Vec<Noun> = Noun::read();
+use std::path::PathBuf;
+
+use crate::types::audio::{play_file_or_print_error, play_file_slowly_or_print_error};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::{Error, Result};
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::gender::hint;
+use crate::types::lint::is_article;
+use crate::types::tags::{Level, Tagged};
+use crate::types::ui::{Colour, Message, Ui};
+use crate::types::utils::{read_csv, remove_random};
+
+pub(crate) const NOUNS_FILE: &str = "nouns.csv";
+
+pub(crate) fn articles(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let mut nouns: Vec<Noun> = Selection::new(data, config)?.nouns(data)?;
     if nouns.is_empty() {
-        println!("No nouns found");
-        return;
+        return ui.finish(vec!["No nouns found".into()]);
     }
 
-    /* There are many nouns, and it is hard to practice and remember all. So I am picking 25 at
-    random and practice on these. */
+    /* There are many nouns, and it is hard to practice and remember all. So I am picking a few
+    (25 by default) at random and practice on these. */
     nouns.shuffle(&mut thread_rng());
-    nouns.truncate(25);
+    nouns.truncate(config.articles.count);
 
     let mut incorrect: HashSet<Noun> = HashSet::new();
     let number_of_nouns = nouns.len();
 
-    println!("------------------------------------------------------------");
-    println!("Loaded {} nouns", number_of_nouns);
-    println!("------------------------------------------------------------");
+    ui.start("nouns", number_of_nouns);
 
-    play_file_or_print_error(Path::new("./audio/program/articles.mp3"));
+    play_file_or_print_error(&data.audio("program").join("articles.mp3"));
 
     while !nouns.is_empty() {
         let noun = nouns.remove(0);
         let mut repeat_noun = false;
-        let mut show_english = false;
 
+        /* A wrong answer is asked again straight away, and once more at the end */
         loop {
-            let prompt = if show_english {
-                format!("{} ({})", noun.singular, noun.english)
-            } else {
-                noun.singular.clone()
-            };
-
-            let input = &play_and_read_line(
-                &format!("{:>3} | {}", nouns.len() + 1, prompt),
-                &noun.singular_file_path(),
-            )
-            .to_lowercase();
-
-            match input.as_str() {
-                "quit" | "exit" => return,
-                "" | "repeat" => {
-                    continue;
-                }
-                "en" | "eng" | "english" => {
-                    show_english = true;
-                    continue;
-                }
-                "die" | "der" | "das" => {
-                    if noun.article.eq_ignore_ascii_case(input) {
-                        println!(
-                            "Correct answer: {} {} ({})",
-                            noun.coloured_article(),
-                            noun.singular,
-                            noun.english
-                        );
-                        noun.play_singular_with_article();
-                        break;
-                    }
-
-                    println!(
-                        "Wrong! Correct answer: {} {} ({})",
-                        noun.coloured_article(),
-                        noun.singular,
-                        noun.english
-                    );
-                    noun.play_singular_with_article();
-                    repeat_noun = true;
-                    continue;
-                }
-                _ => {
-                    println!("Expected the articles der, die, or das");
-                    println!("         quit or exit: to quit");
-                    println!("         en, eng, or english: to show the english translation");
-                    println!("         (blank) or repeat: to replay the audio");
-                    continue;
-                }
+            match attempt(&ArticleExercise(&noun), data, config, ui, nouns.len() + 1)? {
+                Outcome::Quit => return Ok(()),
+                Outcome::Correct | Outcome::Skipped => break,
+                Outcome::Wrong => repeat_noun = true,
             }
         }
 
@@ -94,61 +55,39 @@ pub(crate) fn articles() {
         }
     }
 
-    println!("------------------------------------------------------------");
-    println!(
+    let mut summary = vec![Message::new(format!(
         "Finished {} articles with {} incorrect answers",
         number_of_nouns,
         incorrect.len()
+    ))];
+    summary.extend(
+        incorrect
+            .iter()
+            .map(|noun| Message::new(" - ").append(noun.with_article())),
     );
-    incorrect
-        .iter()
-        .for_each(|noun| println!(" - {} {}", noun.coloured_article(), noun.singular));
-    println!("------------------------------------------------------------");
+    ui.finish(summary)
 }
 
-pub(crate) fn plural() {
-    let mut nouns: Vec<Noun> = Noun::read();
+pub(crate) fn plural(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let mut nouns: Vec<Noun> = Selection::new(data, config)?.nouns(data)?;
     /* Keep it simple for now */
     nouns.retain(|noun| noun.plural.is_some() && noun.singular.len() <= 4);
 
     if nouns.is_empty() {
-        println!("No plural nouns found (matching criteria)");
-        return;
+        return ui.finish(vec!["No plural nouns found (matching criteria)".into()]);
     }
 
-    println!("----------------------------------------");
-    println!("Loaded {} plural nouns", nouns.len());
-    println!("----------------------------------------");
+    ui.start("plural nouns", nouns.len());
 
     loop {
         let noun = remove_random(&mut nouns);
-        let plural = noun.plural.clone().unwrap();
-
         let mut repeat_noun = false;
 
         loop {
-            noun.play_singular();
-            noun.play_singular_with_article();
-
-            let input = read_line(&format!("{} ({}) [ÄÖÜäöüß]", noun.singular, noun.english));
-
-            match input.as_str() {
-                "quit" | "exit" => return,
-                "" | "repeat" => {
-                    continue;
-                }
-                input => {
-                    noun.play_plural();
-                    noun.play_plural_with_article();
-
-                    if plural.eq(input) {
-                        println!("Correct answer: {}", plural);
-                        break;
-                    } else {
-                        println!("Wrong! Correct answer: {}", plural);
-                        repeat_noun = true;
-                    }
-                }
+            match attempt(&PluralExercise(&noun), data, config, ui, nouns.len() + 1)? {
+                Outcome::Quit => return Ok(()),
+                Outcome::Correct | Outcome::Skipped => break,
+                Outcome::Wrong => repeat_noun = true,
             }
         }
 
@@ -158,73 +97,108 @@ pub(crate) fn plural() {
             break;
         }
     }
+
+    ui.finish(vec!["Finished the plural nouns".into()])
 }
 
-#[derive(Debug, serde::Deserialize, Clone, PartialEq, Eq, Hash)]
+/// The article of a noun, asked after playing the noun
+pub(crate) struct ArticleExercise<'a>(pub(crate) &'a Noun);
+
+/// The plural of a noun, asked after playing the noun with and without its article
+pub(crate) struct PluralExercise<'a>(pub(crate) &'a Noun);
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct Noun {
     pub(crate) english: String,
     pub(crate) article: String,
     pub(crate) singular: String,
     pub(crate) plural: Option<String>,
+    pub(crate) tags: Option<String>,
+    pub(crate) level: Option<Level>,
 }
 
 impl Noun {
-    pub(crate) fn read() -> Vec<Noun> {
-        csv::Reader::from_path("nouns.csv")
-            .expect("Failed to read nouns")
-            .deserialize()
-            .map(|r| r.unwrap())
+    pub(crate) fn read(data: &DataDir) -> Result<Vec<Noun>> {
+        let file = data.file(NOUNS_FILE);
+        read_csv(&file)?
+            .into_iter()
+            .map(|(line, noun): (u64, Noun)| match noun.article.as_str() {
+                "der" | "die" | "das" => Ok(noun),
+                article => Err(Error::row(
+                    &file,
+                    line,
+                    format!("unknown article '{}'", article),
+                )),
+            })
             .collect()
     }
 
-    pub(crate) fn singular_file_path(&self) -> PathBuf {
-        Path::new("audio/nouns")
+    pub(crate) fn singular_file_path(&self, data: &DataDir) -> PathBuf {
+        data.audio("nouns")
             .join(&self.singular)
             .with_extension("mp3")
     }
 
-    pub(crate) fn singular_with_article_file_path(&self) -> PathBuf {
-        Path::new("audio/nouns")
-            .join(&format!("{} {}", &self.article, &self.singular))
+    pub(crate) fn singular_with_article_file_path(&self, data: &DataDir) -> PathBuf {
+        data.audio("nouns")
+            .join(format!("{} {}", &self.article, &self.singular))
             .with_extension("mp3")
     }
 
-    pub(crate) fn plural_file_path(&self) -> PathBuf {
-        Path::new("audio/nouns")
-            .join(&self.plural.clone().unwrap())
+    pub(crate) fn plural_file_path(&self, data: &DataDir) -> PathBuf {
+        data.audio("nouns")
+            .join(self.plural.clone().unwrap())
             .with_extension("mp3")
     }
 
-    pub(crate) fn plural_with_article_file_path(&self) -> PathBuf {
-        Path::new("audio/nouns")
-            .join(&format!("die {}", &self.plural.clone().unwrap()))
+    pub(crate) fn plural_with_article_file_path(&self, data: &DataDir) -> PathBuf {
+        data.audio("nouns")
+            .join(format!("die {}", &self.plural.clone().unwrap()))
             .with_extension("mp3")
     }
 
-    fn play_singular(&self) {
-        play_file_or_print_error(&self.singular_file_path());
+    fn play_singular(&self, data: &DataDir) {
+        play_file_or_print_error(&self.singular_file_path(data));
     }
 
-    fn play_singular_with_article(&self) {
-        play_file_or_print_error(&self.singular_with_article_file_path());
+    pub(crate) fn play_singular_with_article(&self, data: &DataDir) {
+        play_file_or_print_error(&self.singular_with_article_file_path(data));
     }
 
-    fn play_plural(&self) {
-        play_file_or_print_error(&self.plural_file_path());
+    fn play_plural(&self, data: &DataDir) {
+        play_file_or_print_error(&self.plural_file_path(data));
     }
 
-    fn play_plural_with_article(&self) {
-        play_file_or_print_error(&self.plural_with_article_file_path());
+    fn play_plural_with_article(&self, data: &DataDir) {
+        play_file_or_print_error(&self.plural_with_article_file_path(data));
     }
 
-    fn coloured_article(&self) -> ColoredString {
+    /// The colour of the article, as shown in the articles mode
+    pub(crate) fn article_colour(&self) -> Option<Colour> {
         match self.article.as_str() {
-            "der" => "der".bright_blue(),
-            "die" => "die".bright_red(),
-            "das" => "das".green(),
-            a => a.normal(),
+            "der" => Some(Colour::Blue),
+            "die" => Some(Colour::Red),
+            "das" => Some(Colour::Green),
+            _ => None,
         }
     }
+
+    /// The singular with the article in its colour, such as der Apfel
+    pub(crate) fn with_article(&self) -> Message {
+        Message::default()
+            .coloured(&self.article, self.article_colour())
+            .text(format!(" {}", self.singular))
+    }
+}
+
+impl Tagged for Noun {
+    fn tag_column(&self) -> Option<&str> {
+        self.tags.as_deref()
+    }
+
+    fn level(&self) -> Option<Level> {
+        self.level
+    }
 }
 
 impl Display for Noun {
@@ -233,21 +207,236 @@ impl Display for Noun {
     }
 }
 
+impl Exercise for ArticleExercise<'_> {
+    fn mode(&self) -> &'static str {
+        "articles"
+    }
+
+    fn item(&self) -> String {
+        self.0.singular.clone()
+    }
+
+    fn variant(&self) -> String {
+        "article".to_string()
+    }
+
+    fn present(&self, data: &DataDir) -> Message {
+        self.0.play_singular(data);
+        self.0.singular.clone().into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the articles der, die, or das"
+    }
+
+    fn check(&self, _config: &Config, answer: &str) -> Option<bool> {
+        let answer = answer.to_lowercase();
+        is_article(&answer).then(|| self.0.article == answer)
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let noun = self.0;
+        let answer = noun.with_article().text(format!(" ({})", noun.english));
+        if correct {
+            ui.show(Message::new("Correct answer: ").append(answer));
+        } else {
+            ui.show(Message::new("Wrong! Correct answer: ").append(answer));
+            if let Some(hint) = hint(noun) {
+                ui.show(hint.into());
+            }
+        }
+        noun.play_singular_with_article(data);
+    }
+
+    fn replay(&self, data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.0.singular_file_path(data));
+        } else {
+            self.0.play_singular(data);
+        }
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        Some(self.0.english.clone())
+    }
+
+    fn solution(&self) -> String {
+        format!("{} {}", self.0.article, self.0.singular)
+    }
+}
+
+impl Exercise for PluralExercise<'_> {
+    fn mode(&self) -> &'static str {
+        "plural"
+    }
+
+    fn item(&self) -> String {
+        self.0.singular.clone()
+    }
+
+    fn variant(&self) -> String {
+        "plural".to_string()
+    }
+
+    fn present(&self, data: &DataDir) -> Message {
+        self.0.play_singular(data);
+        self.0.play_singular_with_article(data);
+        format!("{} ({}) [ÄÖÜäöüß]", self.0.singular, self.0.english).into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the plural"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        let plural = self.0.plural.as_deref()?;
+        Some(config.spelling(plural) == config.spelling(answer))
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        self.0.play_plural(data);
+        self.0.play_plural_with_article(data);
+        let verdict = if correct { "Correct" } else { "Wrong! Correct" };
+        ui.show(format!("{} answer: {}", verdict, self.solution()).into());
+    }
+
+    fn replay(&self, data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.0.singular_file_path(data));
+        } else {
+            self.0.play_singular(data);
+            self.0.play_singular_with_article(data);
+        }
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        Some(self.0.english.clone())
+    }
+
+    fn solution(&self) -> String {
+        self.0.plural.clone().unwrap_or_default()
+    }
+
+    fn choices(&self) -> Vec<String> {
+        let Some(plural) = self.0.plural.clone() else {
+            return vec![];
+        };
+
+        let mut choices = vec![plural];
+        choices.extend(plural_patterns(&self.0.singular));
+        choices
+    }
+}
+
+/// The plurals the singular would have with each of the common patterns (-e, -en, -er, -s, the
+/// umlaut or no ending), of which one is usually the right one
+fn plural_patterns(singular: &str) -> Vec<String> {
+    let mut plurals = vec![
+        singular.to_string(),
+        format!("{}e", singular),
+        format!("{}er", singular),
+        format!("{}s", singular),
+    ];
+    if singular.ends_with('e') {
+        plurals.push(format!("{}n", singular));
+    } else {
+        plurals.push(format!("{}en", singular));
+    }
+    if singular.ends_with("in") {
+        plurals.push(format!("{}nen", singular));
+    }
+    if let Some(umlauted) = umlaut(singular) {
+        plurals.push(format!("{}e", umlauted));
+        plurals.push(format!("{}er", umlauted));
+        plurals.push(umlauted);
+    }
+    plurals
+}
+
+/// The word with the umlaut on its last a, o, u or au, such as Äpfel or Häuser, if it has one
+fn umlaut(word: &str) -> Option<String> {
+    let mut chars: Vec<char> = word.chars().collect();
+    let index = chars.iter().rposition(|c| "aouAOU".contains(*c))?;
+
+    /* In au the umlaut goes on the a, as in Haus and Häuser */
+    let index = match (
+        index.checked_sub(1).map(|before| chars[before]),
+        chars[index],
+    ) {
+        (Some('a' | 'A'), 'u') => index - 1,
+        _ => index,
+    };
+    chars[index] = match chars[index] {
+        'a' => 'ä',
+        'o' => 'ö',
+        'u' => 'ü',
+        'A' => 'Ä',
+        'O' => 'Ö',
+        _ => 'Ü',
+    };
+    Some(chars.into_iter().collect())
+}
+
+#[cfg(test)]
+impl Noun {
+    /// A noun for the tests, which then do not depend on nouns.csv
+    pub(crate) fn example(english: &str, article: &str, singular: &str, plural: &str) -> Self {
+        Noun {
+            english: english.to_string(),
+            article: article.to_string(),
+            singular: singular.to_string(),
+            plural: Some(plural.to_string()),
+            tags: None,
+            level: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
-    use crate::types::nouns::Noun;
+    use crate::types::data::DataDir;
+    use crate::types::nouns::{plural_patterns, umlaut, Noun, NOUNS_FILE};
 
     #[test]
     fn read_all() {
-        let nouns = Noun::read();
+        let nouns = Noun::read(&DataDir::new("")).unwrap();
         assert_eq!(nouns.len(), count_entries_in_csv_file());
     }
 
+    #[test]
+    fn plurals_to_pick_from() {
+        assert_eq!(umlaut("Apfel"), Some("Äpfel".to_string()));
+        assert_eq!(umlaut("Haus"), Some("Häus".to_string()));
+        assert_eq!(umlaut("Lehrer"), None);
+
+        let plurals = plural_patterns("Haus");
+        assert!(plurals.contains(&"Häuser".to_string()));
+        assert!(plurals.contains(&"Hause".to_string()));
+        assert!(plural_patterns("Lampe").contains(&"Lampen".to_string()));
+        assert!(plural_patterns("Lehrerin").contains(&"Lehrerinnen".to_string()));
+    }
+
     fn count_entries_in_csv_file() -> usize {
-        let file = File::open("nouns.csv").expect("Failed to open file");
+        let file = File::open(NOUNS_FILE).expect("Failed to open file");
         BufReader::new(file).lines().count() - 1
     }
 }
@@ -1,5 +1,95 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
 use crate::types::audio::pronounce;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::ui::Ui;
+
+pub(crate) fn numbers(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let range = config.numbers.range.as_ref();
+    pronounce(data, "numbers", config, ui, |stem| {
+        match (range, stem.parse::<u32>()) {
+            (None, _) => true,
+            (Some(range), Ok(number)) => range.contains(number),
+            (Some(_), Err(_)) => false,
+        }
+    })
+}
+
+/// The numbers from `from` to `to` (both included), written as 1..100
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub(crate) struct NumberRange {
+    from: u32,
+    to: u32,
+}
+
+impl NumberRange {
+    pub(crate) fn new(from: u32, to: u32) -> Self {
+        NumberRange { from, to }
+    }
+
+    pub(crate) fn contains(&self, number: u32) -> bool {
+        (self.from..=self.to).contains(&number)
+    }
+}
+
+impl FromStr for NumberRange {
+    type Err = String;
+
+    fn from_str(range: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid range '{}', expected from..to, such as 1..100",
+                range
+            )
+        };
+        let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+        let from = from.trim().parse().map_err(|_| invalid())?;
+        let to = to.trim().parse().map_err(|_| invalid())?;
+        if from > to {
+            return Err(invalid());
+        }
+        Ok(NumberRange::new(from, to))
+    }
+}
+
+impl TryFrom<String> for NumberRange {
+    type Error = String;
+
+    fn try_from(range: String) -> std::result::Result<Self, Self::Error> {
+        range.parse()
+    }
+}
+
+impl From<NumberRange> for String {
+    fn from(range: NumberRange) -> Self {
+        range.to_string()
+    }
+}
+
+impl Display for NumberRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::numbers::NumberRange;
+
+    #[test]
+    fn parse_range() {
+        let range: NumberRange = "1..20".parse().unwrap();
+        assert_eq!(range, NumberRange::new(1, 20));
+        assert!(range.contains(1));
+        assert!(range.contains(20));
+        assert!(!range.contains(21));
+        assert_eq!(range.to_string(), "1..20");
 
-pub(crate) fn numbers() {
-    pronounce("audio/numbers")
+        assert!("20..1".parse::<NumberRange>().is_err());
+        assert!("1-20".parse::<NumberRange>().is_err());
+    }
 }
@@ -1,15 +1,30 @@
+use std::path::PathBuf;
+use std::slice::Iter;
+
 use valid::constraint::CharCount;
 use valid::Validate;
 
+use crate::types::data::DataDir;
+use crate::types::error::{Error, Result};
+use crate::types::tags::{Level, Tagged};
+use crate::types::utils::read_csv;
+
+pub(crate) const PHRASES_FILE: &str = "phrases.csv";
+
 #[derive(Debug, serde::Deserialize, serde::Serialize, Ord, PartialOrd, Eq, PartialEq)]
 pub(crate) struct Phrase {
-    english: String,
-    german: String,
+    pub(crate) english: String,
+    pub(crate) german: String,
+    pub(crate) tags: Option<String>,
+    pub(crate) level: Option<Level>,
 }
 
 #[derive(Debug)]
 pub(crate) struct Phrases {
+    file: PathBuf,
     data: Vec<Phrase>,
+    /// Whether phrases were added since the file was read
+    changed: bool,
 }
 
 impl Phrase {
@@ -17,9 +32,17 @@ impl Phrase {
         Phrase {
             english: Self::check_phrase_argument("English phrase", english),
             german: Self::check_phrase_argument("German phrase", german),
+            tags: None,
+            level: None,
         }
     }
 
+    pub(crate) fn audio_file_path(&self, data: &DataDir) -> PathBuf {
+        data.audio("phrases")
+            .join(&self.german)
+            .with_extension("mp3")
+    }
+
     fn check_phrase_argument(field_name: &'static str, phrase: String) -> String {
         phrase
             .validate(field_name, &CharCount::MinMax(12, 64))
@@ -30,38 +53,69 @@ impl Phrase {
 }
 
 impl Phrases {
-    pub(crate) fn read() -> Self {
-        let data = csv::Reader::from_path("phrases.csv")
-            .expect("Failed to read phrases")
-            .deserialize()
-            .map(|r| r.unwrap())
+    pub(crate) fn read(data: &DataDir) -> Result<Self> {
+        let file = data.file(PHRASES_FILE);
+        let data = read_csv(&file)?
+            .into_iter()
+            .map(|(_, phrase)| phrase)
             .collect();
-        Phrases { data }
+        Ok(Phrases {
+            file,
+            data,
+            changed: false,
+        })
     }
 
     pub(crate) fn append(&mut self, phrases: &mut Vec<Phrase>) {
+        self.changed |= !phrases.is_empty();
         self.data.append(phrases);
-        self.data.dedup_by(|a, b| a.german == b.german);
+        /* Sorted first, as only neighbouring duplicates are removed */
         self.data.sort_by(|a, b| a.german.cmp(&b.german));
+        self.data.dedup_by(|a, b| a.german == b.german);
     }
 
-    pub(crate) fn write(&self) {
-        let mut writer = csv::Writer::from_path("phrases.csv").expect("Failed to write phrases");
+    /// Writes the phrases back, after which dropping them does not write them again
+    pub(crate) fn write(&mut self) -> Result<()> {
+        let mut writer =
+            csv::Writer::from_path(&self.file).map_err(|e| Error::csv(&self.file, &e))?;
         for phrase in self.data.iter() {
             writer
                 .serialize(phrase)
-                .expect("Failed to serialize phrase");
+                .map_err(|e| Error::csv(&self.file, &e))?;
         }
+        self.changed = false;
+        Ok(())
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, Phrase> {
+        self.data.iter()
     }
 
+    #[cfg(test)]
     pub(crate) fn len(&self) -> usize {
         self.data.len()
     }
 }
 
+impl Tagged for Phrase {
+    fn tag_column(&self) -> Option<&str> {
+        self.tags.as_deref()
+    }
+
+    fn level(&self) -> Option<Level> {
+        self.level
+    }
+}
+
 impl Drop for Phrases {
     fn drop(&mut self) {
-        self.write();
+        if !self.changed {
+            return;
+        }
+
+        if let Err(e) = self.write() {
+            eprintln!("{}", e);
+        }
     }
 }
 
@@ -70,16 +124,41 @@ mod tests {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
-    use crate::types::phrases::Phrases;
+    use crate::types::data::DataDir;
+    use crate::types::phrases::{Phrase, Phrases, PHRASES_FILE};
 
     #[test]
     fn read_all() {
-        let phrases = Phrases::read();
+        let phrases = Phrases::read(&DataDir::new("")).unwrap();
         assert_eq!(phrases.len(), count_entries_in_csv_file());
     }
 
+    #[test]
+    fn append_without_duplicates() {
+        let phrase =
+            |german: &str| Phrase::new(german.to_string(), "A phrase in English".to_string());
+        let mut phrases = Phrases {
+            file: PHRASES_FILE.into(),
+            data: vec![phrase("Guten Tag, Anna"), phrase("Tschüss, bis morgen")],
+            changed: false,
+        };
+
+        phrases.append(&mut vec![
+            phrase("Danke, sehr nett"),
+            phrase("Guten Tag, Anna"),
+        ]);
+        let german: Vec<&str> = phrases.iter().map(|p| p.german.as_str()).collect();
+        assert_eq!(
+            german,
+            ["Danke, sehr nett", "Guten Tag, Anna", "Tschüss, bis morgen"]
+        );
+
+        /* Not written back to the real phrases */
+        phrases.changed = false;
+    }
+
     fn count_entries_in_csv_file() -> usize {
-        let file = File::open("phrases.csv").expect("Failed to open file");
+        let file = File::open(PHRASES_FILE).expect("Failed to open file");
         BufReader::new(file).lines().count() - 1
     }
 }
@@ -0,0 +1,152 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::utils::read_csv;
+
+/// One answer given in one of the drills
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Attempt {
+    /// Seconds since the Unix epoch
+    pub(crate) timestamp: u64,
+    pub(crate) mode: String,
+    /// What was practised, such as the noun or the infinitive of the verb
+    pub(crate) item: String,
+    /// Which form of the item was asked, such as the pronoun and tense of a verb
+    pub(crate) variant: String,
+    pub(crate) correct: bool,
+}
+
+/// The answers given across all sessions, kept in an append-only CSV file
+pub(crate) struct History {
+    attempts: Vec<Attempt>,
+}
+
+const HISTORY_FILE: &str = "history.csv";
+
+impl History {
+    pub(crate) fn record(data: &DataDir, mode: &str, item: &str, variant: &str, correct: bool) {
+        let attempt = Attempt {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            mode: mode.to_string(),
+            item: item.to_string(),
+            variant: variant.to_string(),
+            correct,
+        };
+
+        /* Losing a record is not worth interrupting the practice for */
+        let file = data.state(HISTORY_FILE);
+        if let Err(e) = Self::append(&Self::file(data), &file, &attempt) {
+            eprintln!("Failed to record progress in {} ({})", file.display(), e);
+        }
+    }
+
+    /// All the answers, oldest first, or none if nothing was practised yet
+    pub(crate) fn read(data: &DataDir) -> Result<Self> {
+        let file = Self::file(data);
+        if !file.exists() {
+            return Ok(History { attempts: vec![] });
+        }
+
+        let attempts = read_csv(&file)?
+            .into_iter()
+            .map(|(_, attempt)| attempt)
+            .collect();
+        Ok(History { attempts })
+    }
+
+    pub(crate) fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Whether the last answer for the item, in the given mode, was correct
+    pub(crate) fn learned(&self, mode: &str, item: &str) -> bool {
+        self.last(|attempt| attempt.mode == mode && attempt.item == item)
+    }
+
+    /// Whether the last answer for the variant (such as the sentence), in the given mode, was
+    /// correct
+    pub(crate) fn learned_variant(&self, mode: &str, variant: &str) -> bool {
+        self.last(|attempt| attempt.mode == mode && attempt.variant == variant)
+    }
+
+    fn last(&self, matches: impl Fn(&Attempt) -> bool) -> bool {
+        self.attempts
+            .iter()
+            .rev()
+            .find(|attempt| matches(attempt))
+            .is_some_and(|attempt| attempt.correct)
+    }
+
+    /// The history in the state directory, or the one the earlier versions kept in the data
+    /// directory until the next answer moves it over
+    fn file(data: &DataDir) -> PathBuf {
+        let file = data.state(HISTORY_FILE);
+        let earlier = data.file(HISTORY_FILE);
+        if !file.exists() && earlier.is_file() {
+            earlier
+        } else {
+            file
+        }
+    }
+
+    /// Appends the answer to the history, starting from the earlier one if the file is new
+    fn append(
+        earlier: &Path,
+        path: &Path,
+        attempt: &Attempt,
+    ) -> std::result::Result<(), csv::Error> {
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if earlier != path {
+                fs::copy(earlier, path)?;
+            }
+        }
+
+        let new_file = !path.exists();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(new_file)
+            .from_writer(file);
+        writer.serialize(attempt)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::progress::{Attempt, History};
+
+    #[test]
+    fn learned_from_last_attempt() {
+        let attempt = |item: &str, correct: bool| Attempt {
+            timestamp: 0,
+            mode: "articles".to_string(),
+            item: item.to_string(),
+            variant: String::new(),
+            correct,
+        };
+        let history = History {
+            attempts: vec![
+                attempt("Apfel", false),
+                attempt("Apfel", true),
+                attempt("Brot", true),
+                attempt("Brot", false),
+            ],
+        };
+
+        assert!(history.learned("articles", "Apfel"));
+        assert!(!history.learned("articles", "Brot"));
+        assert!(!history.learned("articles", "Käse"));
+        assert!(!history.learned("plural", "Apfel"));
+    }
+}
@@ -0,0 +1,323 @@
+use std::io::{stdin, stdout, BufRead, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::types::audio::{forward_playback, take_forwarded};
+use crate::types::error::{Error, Result};
+use crate::types::ui::{Colour, Input, Message, Ui};
+
+/// The typed commands accepted besides the answer
+const COMMANDS: [&str; 5] = ["replay", "slow", "english", "skip", "quit"];
+
+/// How the drills talk to another program instead of the user
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Protocol {
+    /// One JSON object per line: the events on stdout and the answers on stdin
+    Jsonl,
+}
+
+/// What happened in a drill, as sent to the browser or to another program
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub(crate) enum Event {
+    Start {
+        title: String,
+        items: usize,
+    },
+    /// An audio file to play, in the order of the events
+    Audio {
+        file: String,
+        speed: f32,
+    },
+    /// The drill waits for the next answer
+    Prompt {
+        /// What is asked, such as Apfel/article or wohnen/praesens ich
+        item: Option<String>,
+        text: String,
+        parts: Vec<Part>,
+        remaining: usize,
+        /// The last audio file played before the prompt, if any
+        audio: Option<String>,
+        commands: Vec<&'static str>,
+    },
+    Show {
+        text: String,
+        parts: Vec<Part>,
+    },
+    /// The answer to the item was checked
+    Score {
+        item: Option<String>,
+        correct: bool,
+    },
+    Help {
+        expected: String,
+    },
+    Finish {
+        summary: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+    /// The drill is over
+    Closed,
+}
+
+/// A part of a message, such as the article in its colour
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Part {
+    text: String,
+    colour: Option<Colour>,
+}
+
+/// The answer, which is either the answer itself or one of the typed commands (such as replay or
+/// skip), as in {"input": "der"}
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct Answer {
+    pub(crate) input: String,
+}
+
+/// How the audio files are named in the events, such as their link for the browser
+type AudioName = Box<dyn Fn(&Path) -> Option<String> + Send>;
+
+/// Where the events go and where the answers come from, such as the browser or another program
+pub(crate) trait Transport {
+    /// Sends the events, the last of which is a prompt, and waits for the answer
+    fn exchange(&mut self, events: Vec<Event>) -> Result<Input>;
+
+    /// Sends the events without waiting for an answer
+    fn send(&mut self, events: Vec<Event>);
+}
+
+/// The interface of a drill run by the browser or another program, which plays the audio files
+/// itself.  The events are sent when the drill waits for an answer, and when it is over.
+pub(crate) struct Remote<T: Transport> {
+    transport: T,
+    name: AudioName,
+    item: Option<String>,
+    events: Vec<Event>,
+}
+
+/// The events on stdout and the answers on stdin, one JSON object per line
+pub(crate) struct Jsonl;
+
+impl<T: Transport> Remote<T> {
+    /// Passes the audio files played on this thread on instead of playing them, which is why it
+    /// has to be created on the thread running the drill
+    pub(crate) fn new(
+        transport: T,
+        name: impl Fn(&Path) -> Option<String> + Send + 'static,
+    ) -> Self {
+        forward_playback();
+        Remote {
+            transport,
+            name: Box::new(name),
+            item: None,
+            events: vec![],
+        }
+    }
+
+    /// Reports the error that ended the drill
+    pub(crate) fn fail(&mut self, error: &Error) {
+        self.push(Event::Error {
+            message: error.to_string(),
+        });
+    }
+
+    /// Adds the event after the audio files played so far, so that they are played in order
+    fn push(&mut self, event: Event) {
+        self.take_audio();
+        self.events.push(event);
+    }
+
+    fn take_audio(&mut self) {
+        for (file, speed) in take_forwarded() {
+            if let Some(file) = (self.name)(&file) {
+                self.events.push(Event::Audio { file, speed });
+            }
+        }
+    }
+}
+
+impl<T: Transport> Ui for Remote<T> {
+    fn start(&mut self, title: &str, items: usize) {
+        self.push(Event::Start {
+            title: title.to_string(),
+            items,
+        });
+    }
+
+    fn item(&mut self, id: &str) {
+        self.item = Some(id.to_string());
+    }
+
+    fn ask(&mut self, prompt: &Message, remaining: usize) -> Result<Input> {
+        self.take_audio();
+        let audio = self.events.iter().rev().find_map(|event| match event {
+            Event::Audio { file, .. } => Some(file.clone()),
+            _ => None,
+        });
+
+        self.push(Event::Prompt {
+            item: self.item.clone(),
+            text: prompt.plain_text(),
+            parts: parts(prompt),
+            remaining,
+            audio,
+            commands: COMMANDS.to_vec(),
+        });
+        let events = std::mem::take(&mut self.events);
+        self.transport.exchange(events)
+    }
+
+    fn show(&mut self, message: Message) {
+        self.push(Event::Show {
+            text: message.plain_text(),
+            parts: parts(&message),
+        });
+    }
+
+    fn score(&mut self, correct: bool) {
+        self.push(Event::Score {
+            item: self.item.clone(),
+            correct,
+        });
+    }
+
+    fn help(&mut self, expected: &str) {
+        self.push(Event::Help {
+            expected: expected.to_string(),
+        });
+    }
+
+    fn finish(&mut self, summary: Vec<Message>) -> Result<()> {
+        self.push(Event::Finish {
+            summary: summary.iter().map(Message::plain_text).collect(),
+        });
+        Ok(())
+    }
+}
+
+/* The drill is over, whether it finished or was quit */
+impl<T: Transport> Drop for Remote<T> {
+    fn drop(&mut self) {
+        self.push(Event::Closed);
+        let events = std::mem::take(&mut self.events);
+        self.transport.send(events);
+    }
+}
+
+impl Transport for Jsonl {
+    fn exchange(&mut self, events: Vec<Event>) -> Result<Input> {
+        self.send(events);
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stdin().lock().read_line(&mut line).map_err(Error::Input)? == 0 {
+                return Ok(Input::Quit);
+            }
+            match serde_json::from_str::<Answer>(&line) {
+                Ok(answer) => return Ok(Input::parse(&answer.input)),
+                Err(e) => self.send(vec![Event::Error {
+                    message: format!("Expected an answer such as {{\"input\": \"der\"}}: {}", e),
+                }]),
+            }
+        }
+    }
+
+    fn send(&mut self, events: Vec<Event>) {
+        let mut stdout = stdout().lock();
+        for event in events {
+            /* The events only hold strings and numbers, which are always written */
+            if let Ok(line) = serde_json::to_string(&event) {
+                let _ = writeln!(stdout, "{}", line);
+            }
+        }
+        let _ = stdout.flush();
+    }
+}
+
+fn parts(message: &Message) -> Vec<Part> {
+    message
+        .parts
+        .iter()
+        .map(|(text, colour)| Part {
+            text: text.clone(),
+            colour: *colour,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    use crate::types::audio::play_file_or_print_error;
+    use crate::types::error::Result;
+    use crate::types::protocol::{parts, Event, Remote, Transport};
+    use crate::types::ui::{Colour, Input, Message, Ui};
+
+    /// Keeps the events, and answers der to every prompt
+    struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+    impl Transport for Recorder {
+        fn exchange(&mut self, events: Vec<Event>) -> Result<Input> {
+            self.send(events);
+            Ok(Input::Answer("der".to_string()))
+        }
+
+        fn send(&mut self, events: Vec<Event>) {
+            self.0.lock().unwrap().extend(events);
+        }
+    }
+
+    #[test]
+    fn events_in_order() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut ui = Remote::new(Recorder(Arc::clone(&events)), |file: &Path| {
+            Some(file.display().to_string())
+        });
+        ui.start("nouns", 1);
+        ui.item("Apfel/article");
+        play_file_or_print_error(Path::new("Cargo.toml"));
+        assert_eq!(
+            ui.ask(&"Apfel".into(), 1).unwrap(),
+            Input::Answer("der".to_string())
+        );
+        ui.score(true);
+        drop(ui);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(&events[1], Event::Audio { file, .. } if file == "Cargo.toml"));
+        assert!(matches!(
+            &events[2],
+            Event::Prompt { item: Some(item), audio: Some(audio), .. }
+                if item == "Apfel/article" && audio == "Cargo.toml"
+        ));
+        assert_eq!(
+            events[3],
+            Event::Score {
+                item: Some("Apfel/article".to_string()),
+                correct: true
+            }
+        );
+        assert_eq!(events[4], Event::Closed);
+    }
+
+    #[test]
+    fn events_as_json() {
+        let message = Message::new("Correct answer: ").coloured("der", Some(Colour::Blue));
+        let event = Event::Show {
+            text: message.plain_text(),
+            parts: parts(&message),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"show","text":"Correct answer: der","parts":[{"text":"Correct answer: ","colour":null},{"text":"der","colour":"blue"}]}"#
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::audio::play_file_or_print_error;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::phrases::{Phrase, Phrases};
+use crate::types::ui::{Message, Ui};
+use crate::types::verbs::{Pronoun, Tense, Verb};
+
+/// A sentence with the conjugated verb left out, such as: Wir ___ in Berlin. (arbeiten)
+struct Gap<'a> {
+    verb: &'a Verb,
+    /// The words before and after the gap
+    before: String,
+    after: String,
+    answer: String,
+    source: Source<'a>,
+}
+
+enum Source<'a> {
+    /// The sentence comes from the phrases and has its own recording
+    Phrase(&'a Phrase),
+    /// The sentence is made up from the pronoun and the conjugation
+    Generated(&'static Pronoun),
+}
+
+/* Keep the session varied when a common verb, like sein, is found in many phrases */
+const MAX_PHRASES_PER_VERB: usize = 3;
+
+pub(crate) fn sentences(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let selection = Selection::new(data, config)?;
+    let verbs = selection.verbs(data)?;
+    let phrases = Phrases::read(data)?;
+    let phrases = selection.phrases(&phrases);
+
+    let mut gaps: Vec<Gap> = verbs
+        .iter()
+        .flat_map(|verb| Gap::for_verb(verb, &phrases))
+        .collect();
+    if gaps.is_empty() {
+        return ui.finish(vec!["No verbs found".into()]);
+    }
+    gaps.shuffle(&mut thread_rng());
+
+    ui.start("sentences", gaps.len());
+
+    while !gaps.is_empty() {
+        let gap = gaps.remove(0);
+        match attempt(&gap, data, config, ui, gaps.len() + 1)? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Wrong => gaps.push(gap),
+            Outcome::Correct | Outcome::Skipped => {}
+        }
+    }
+
+    ui.finish(vec!["Finished the sentences".into()])
+}
+
+impl<'a> Gap<'a> {
+    /// Finds the phrases that contain one of the present tense forms of the verb, or makes up a
+    /// sentence if there are none
+    fn for_verb(verb: &'a Verb, phrases: &[&'a Phrase]) -> Vec<Gap<'a>> {
+        let mut forms: Vec<String> = Tense::Praesens
+            .pronouns()
+            .filter_map(|pronoun| verb.conjugation(pronoun, &Tense::Praesens))
+            .filter_map(|conjugation| finite_verb(&conjugation))
+            .collect();
+        forms.sort();
+        forms.dedup();
+
+        let mut gaps: Vec<Gap> = phrases
+            .iter()
+            .filter_map(|phrase| Gap::from_phrase(verb, phrase, &forms))
+            .collect();
+        gaps.shuffle(&mut thread_rng());
+        gaps.truncate(MAX_PHRASES_PER_VERB);
+
+        if gaps.is_empty() {
+            gaps.extend(Gap::generate(verb));
+        }
+        gaps
+    }
+
+    fn from_phrase(verb: &'a Verb, phrase: &'a Phrase, forms: &[String]) -> Option<Gap<'a>> {
+        let words: Vec<&str> = phrase.german.split_whitespace().collect();
+        let index = words.iter().position(|word| {
+            let word = strip_punctuation(word).to_lowercase();
+            forms.iter().any(|form| form.to_lowercase() == word)
+        })?;
+
+        /* Keep the punctuation attached to the verb, as in: Wie ___? */
+        let word = words[index];
+        let answer = strip_punctuation(word);
+        let start = word.find(answer).unwrap_or(0);
+        let before = [&words[..index], &[&word[..start]]].concat().join(" ");
+        let after = [&[&word[start + answer.len()..]], &words[index + 1..]]
+            .concat()
+            .join(" ");
+
+        Some(Gap {
+            verb,
+            before: before.trim_end().to_string(),
+            after: after.trim_start().to_string(),
+            answer: answer.to_string(),
+            source: Source::Phrase(phrase),
+        })
+    }
+
+    fn generate(verb: &'a Verb) -> Option<Gap<'a>> {
+        let pronoun = *Tense::Praesens.distinct_pronouns().first()?;
+        let conjugation = verb.conjugation(pronoun, &Tense::Praesens)?;
+        let (answer, rest) = match conjugation.split_once(' ') {
+            Some((answer, rest)) => (answer.to_string(), format!("{}.", rest)),
+            None => (conjugation.clone(), ".".to_string()),
+        };
+
+        Some(Gap {
+            verb,
+            before: capitalise(&pronoun.to_string()),
+            after: rest,
+            answer,
+            source: Source::Generated(pronoun),
+        })
+    }
+
+    fn sentence(&self) -> String {
+        self.with(&self.answer)
+    }
+
+    /// The sentence with the given text in the gap
+    fn with(&self, text: &str) -> String {
+        let mut sentence = self.before.clone();
+        if !sentence.is_empty() {
+            sentence.push(' ');
+        }
+        sentence.push_str(text);
+        if self.after.starts_with(char::is_alphanumeric) {
+            sentence.push(' ');
+        }
+        sentence.push_str(&self.after);
+        sentence
+    }
+}
+
+impl Exercise for Gap<'_> {
+    fn mode(&self) -> &'static str {
+        "sentences"
+    }
+
+    fn item(&self) -> String {
+        self.verb.infinitive()
+    }
+
+    fn variant(&self) -> String {
+        self.sentence()
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        format!("{} ({})", self.with("___"), self.verb.infinitive()).into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the conjugated verb"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        Some(
+            config.spelling(&self.answer.to_lowercase()) == config.spelling(&answer.to_lowercase()),
+        )
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let verdict = if correct { "Correct" } else { "Wrong! Correct" };
+        ui.show(format!("{} answer: {}", verdict, self.sentence()).into());
+
+        match self.source {
+            Source::Phrase(phrase) => play_file_or_print_error(&phrase.audio_file_path(data)),
+            Source::Generated(pronoun) => {
+                self.verb.play_conjugation(data, pronoun, &Tense::Praesens)
+            }
+        }
+    }
+
+    /* The recording would give the answer away */
+    fn replay(&self, _data: &DataDir, _slow: bool) -> bool {
+        false
+    }
+
+    fn english(&self) -> Option<String> {
+        match self.source {
+            Source::Phrase(phrase) => Some(phrase.english.clone()),
+            Source::Generated(_) => Some(self.verb.english.clone()),
+        }
+    }
+
+    fn solution(&self) -> String {
+        self.sentence()
+    }
+}
+
+/// The first word of the conjugation, which is the one that changes with the pronoun
+fn finite_verb(conjugation: &str) -> Option<String> {
+    conjugation.split_whitespace().next().map(String::from)
+}
+
+fn strip_punctuation(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphabetic())
+}
+
+fn capitalise(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::data::DataDir;
+    use crate::types::phrases::Phrases;
+    use crate::types::sentences::Gap;
+    use crate::types::verbs::Verb;
+
+    #[test]
+    fn gap_in_phrase() {
+        let data = DataDir::new("");
+        let verbs = Verb::read(&data).unwrap();
+        let phrases = Phrases::read(&data).unwrap();
+        let wohnen = verbs
+            .iter()
+            .find(|verb| verb.infinitive() == "wohnen")
+            .unwrap();
+
+        let gaps = Gap::for_verb(wohnen, &phrases.iter().collect::<Vec<_>>());
+        let gap = gaps
+            .iter()
+            .find(|gap| gap.before == "Er" && gap.after == "bei der Frau.")
+            .unwrap();
+
+        assert_eq!(gap.answer, "wohnt");
+        assert_eq!(gap.sentence(), "Er wohnt bei der Frau.");
+        assert_eq!(gap.with("___"), "Er ___ bei der Frau.");
+    }
+}
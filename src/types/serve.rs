@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Args as ClapArgs;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+
+use crate::types::alphabet::alphabet;
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::{Error, Result};
+use crate::types::listen::listen;
+use crate::types::nouns::{articles, plural};
+use crate::types::numbers::numbers;
+use crate::types::phrases::Phrases;
+use crate::types::protocol::{Answer, Event, Remote, Transport};
+use crate::types::sentences::sentences;
+use crate::types::time::{time, TimeArgs};
+use crate::types::translation::translation;
+use crate::types::ui::{Input, Ui};
+use crate::types::verbs::verbs;
+
+/// The page with the drills, which talks to the API below
+const INDEX: &str = include_str!("serve.html");
+
+/// What is escaped in the links to the audio files, such as the spaces and umlauts
+const ESCAPED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'.').remove(b'-').remove(b'_');
+
+/// The largest body accepted, which is plenty for an answer
+const MAX_BODY: usize = 4096;
+
+/// How long a drill waits for the next answer before the browser is taken to be gone
+const IDLE: Duration = Duration::from_secs(30 * 60);
+
+/// The drills that can be practised in the browser (speak needs the microphone of the terminal)
+const DRILLS: [&str; 9] = [
+    "articles",
+    "plural",
+    "verbs",
+    "sentences",
+    "translation",
+    "listen",
+    "numbers",
+    "alphabet",
+    "time",
+];
+
+#[derive(ClapArgs, Clone, Debug)]
+pub(crate) struct ServeArgs {
+    /// The port to listen on, on localhost only
+    #[clap(long, default_value_t = 8080)]
+    pub(crate) port: u16,
+}
+
+/// The events of a session, up to the next prompt (or until the drill is over)
+#[derive(Serialize)]
+struct Reply {
+    session: u64,
+    events: Vec<Event>,
+}
+
+/// A drill that runs on its own thread, and waits for the answers from the browser
+struct Session {
+    answers: Sender<Input>,
+    events: Receiver<Vec<Event>>,
+    /// When the drill started waiting for the answer
+    waiting: Instant,
+}
+
+/// The drill side of a session: the events go to the request waiting for them, and the answers
+/// come from the next request
+struct Channel {
+    sender: Sender<Vec<Event>>,
+    answers: Receiver<Input>,
+}
+
+struct Server {
+    port: u16,
+    data: DataDir,
+    config: Config,
+    sessions: Mutex<HashMap<u64, Session>>,
+    next_session: AtomicU64,
+}
+
+/// A parsed HTTP request, with only what the API needs
+struct Request {
+    method: String,
+    path: String,
+    host: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+/// Serves the drills, the audio files and the word lists on localhost until interrupted
+pub(crate) fn serve(data: &DataDir, config: &Config, options: &ServeArgs) -> Result<()> {
+    let address = format!("127.0.0.1:{}", options.port);
+    let listener = TcpListener::bind(&address).map_err(|e| Error::serve(&address, e))?;
+
+    println!("------------------------------------------------------------");
+    println!("Serving the drills on http://{} (Ctrl-C to stop)", address);
+    println!("------------------------------------------------------------");
+
+    let server = Arc::new(Server {
+        port: options.port,
+        data: data.clone(),
+        config: config.clone(),
+        sessions: Mutex::new(HashMap::new()),
+        next_session: AtomicU64::new(1),
+    });
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+
+        let server = Arc::clone(&server);
+        thread::spawn(move || {
+            if let Err(e) = server.handle(stream) {
+                println!("Failed to answer a request: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+impl Server {
+    fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let response = match read_request(&mut stream)? {
+            /* Another site can point its own name at 127.0.0.1, but not send this Host header */
+            Ok(request) if !is_local(&request.host, self.port) => {
+                Response::error("403 Forbidden", "Only served on localhost")
+            }
+            Ok(request) => self.respond(&request),
+            Err(response) => response,
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.content_type,
+            response.body.len()
+        )?;
+        stream.write_all(&response.body)?;
+        stream.flush()
+    }
+
+    fn respond(&self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        let result = match (request.method.as_str(), segments.as_slice()) {
+            ("GET", [""]) => Ok(Response::ok(
+                "text/html; charset=utf-8",
+                INDEX.as_bytes().to_vec(),
+            )),
+            ("GET", ["audio", ..]) => self.audio(&segments[1..]),
+            ("GET", ["api", "drills"]) => Ok(Response::json(&DRILLS)),
+            ("GET", ["api", "nouns"]) => self.nouns(),
+            ("GET", ["api", "verbs"]) => self.verbs(),
+            ("GET", ["api", "phrases"]) => self.phrases(),
+            ("POST", ["api", "drills", drill]) => self.start(drill),
+            ("POST", ["api", "sessions", id]) => self.answer(id, &request.body),
+            _ => Ok(Response::error("404 Not Found", "Not found")),
+        };
+        result.unwrap_or_else(|e| Response::error("500 Internal Server Error", &e.to_string()))
+    }
+
+    /// An audio file, such as /audio/nouns/der%20Apfel.mp3
+    fn audio(&self, segments: &[&str]) -> Result<Response> {
+        let mut file = self.data.file("audio");
+        for segment in segments {
+            let segment = percent_decode_str(segment).decode_utf8_lossy();
+            /* Only the files in the audio directory are served */
+            match Path::new(segment.as_ref()).components().next() {
+                Some(Component::Normal(name)) if name == segment.as_ref() => file.push(name),
+                _ => return Ok(Response::error("404 Not Found", "Not found")),
+            }
+        }
+
+        if !file.is_file() {
+            return Ok(Response::error("404 Not Found", "Not found"));
+        }
+        let content_type = match file.extension().and_then(|extension| extension.to_str()) {
+            Some("mp3") => "audio/mpeg",
+            Some("wav") => "audio/wav",
+            _ => "application/octet-stream",
+        };
+        let body = fs::read(&file).map_err(|e| Error::io(&file, e))?;
+        Ok(Response::ok(content_type, body))
+    }
+
+    fn nouns(&self) -> Result<Response> {
+        let selection = Selection::new(&self.data, &self.config)?;
+        Ok(Response::json(&selection.nouns(&self.data)?))
+    }
+
+    fn verbs(&self) -> Result<Response> {
+        let selection = Selection::new(&self.data, &self.config)?;
+        Ok(Response::json(&selection.verbs(&self.data)?))
+    }
+
+    fn phrases(&self) -> Result<Response> {
+        let selection = Selection::new(&self.data, &self.config)?;
+        let phrases = Phrases::read(&self.data)?;
+        Ok(Response::json(&selection.phrases(&phrases)))
+    }
+
+    /// Starts the drill on its own thread, and returns its events up to the first prompt
+    fn start(&self, drill: &str) -> Result<Response> {
+        if !DRILLS.contains(&drill) {
+            return Ok(Response::error("404 Not Found", "Unknown drill"));
+        }
+
+        let (sender, events) = channel();
+        let (answers, receiver) = channel();
+        let (drill, data, config) = (drill.to_string(), self.data.clone(), self.config.clone());
+        thread::spawn(move || {
+            let audio = data.file("audio");
+            let channel = Channel {
+                sender,
+                answers: receiver,
+            };
+            let mut ui = Remote::new(channel, move |file: &Path| audio_url(&audio, file));
+            if let Err(e) = run(&drill, &data, &config, &mut ui) {
+                ui.fail(&e);
+            }
+        });
+
+        /* The drills of the sessions left idle end, as their answers are dropped */
+        self.sessions
+            .lock()
+            .unwrap()
+            .retain(|_, session| session.waiting.elapsed() < IDLE);
+
+        let id = self.next_session.fetch_add(1, Ordering::Relaxed);
+        let session = Session {
+            answers,
+            events,
+            waiting: Instant::now(),
+        };
+        self.reply(id, session)
+    }
+
+    /// Passes the answer to the drill, and returns its events up to the next prompt
+    fn answer(&self, id: &str, body: &[u8]) -> Result<Response> {
+        let session = id
+            .parse()
+            .ok()
+            .and_then(|id| self.sessions.lock().unwrap().remove(&id).map(|s| (id, s)));
+        let Some((id, session)) = session else {
+            return Ok(Response::error("404 Not Found", "Unknown session"));
+        };
+        let Ok(answer) = serde_json::from_slice::<Answer>(body) else {
+            self.sessions.lock().unwrap().insert(id, session);
+            return Ok(Response::error(
+                "400 Bad Request",
+                "Expected an answer such as {\"input\": \"der\"}",
+            ));
+        };
+
+        /* The drill is gone if it failed, which the next receive reports */
+        let _ = session.answers.send(Input::parse(&answer.input));
+        self.reply(id, session)
+    }
+
+    /// Waits for the events of the session, which is kept until the drill is over (or left idle)
+    fn reply(&self, id: u64, mut session: Session) -> Result<Response> {
+        let events = session
+            .events
+            .recv()
+            .unwrap_or_else(|_| vec![Event::Closed]);
+        if events.last() != Some(&Event::Closed) {
+            session.waiting = Instant::now();
+            self.sessions.lock().unwrap().insert(id, session);
+        }
+
+        Ok(Response::json(&Reply {
+            session: id,
+            events,
+        }))
+    }
+}
+
+fn run(drill: &str, data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    match drill {
+        "articles" => articles(data, config, ui),
+        "plural" => plural(data, config, ui),
+        "verbs" => verbs(data, config, ui),
+        "sentences" => sentences(data, config, ui),
+        "translation" => translation(data, config, ui),
+        "listen" => listen(data, config, ui),
+        "numbers" => numbers(data, config, ui),
+        "time" => time(data, config, &TimeArgs::default(), ui),
+        _ => alphabet(data, config, ui),
+    }
+}
+
+impl Transport for Channel {
+    fn exchange(&mut self, events: Vec<Event>) -> Result<Input> {
+        self.send(events);
+        /* The browser is gone when the session was dropped, or left idle */
+        Ok(self.answers.recv_timeout(IDLE).unwrap_or(Input::Quit))
+    }
+
+    fn send(&mut self, events: Vec<Event>) {
+        /* Nobody is waiting any more when the session was dropped */
+        let _ = self.sender.send(events);
+    }
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: Vec<u8>) -> Self {
+        Response {
+            status: "200 OK",
+            content_type,
+            body,
+        }
+    }
+
+    fn json(value: &impl Serialize) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Response::ok("application/json", body),
+            Err(e) => Response::error("500 Internal Server Error", &e.to_string()),
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Response {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: message.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Reads the request line, the headers and the body (of Content-Length bytes), or the error to
+/// respond with if the request is malformed or too large
+fn read_request(stream: &mut TcpStream) -> std::io::Result<std::result::Result<Request, Response>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(target)) = (words.next(), words.next()) else {
+        return Ok(Err(Response::error("400 Bad Request", "Malformed request")));
+    };
+    let (method, path) = (
+        method.to_string(),
+        target.split('?').next().unwrap_or_default().to_string(),
+    );
+
+    let (mut host, mut length) = (String::new(), 0);
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            } else if name.trim().eq_ignore_ascii_case("host") {
+                host = value.trim().to_string();
+            }
+        }
+    }
+
+    /* Checked before anything is allocated for it */
+    if length > MAX_BODY {
+        return Ok(Err(Response::error(
+            "413 Payload Too Large",
+            "The request body is too large",
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok(Request {
+        method,
+        path,
+        host,
+        body,
+    }))
+}
+
+/// Whether the Host header names this server on localhost, such as localhost:8080
+fn is_local(host: &str, port: u16) -> bool {
+    ["localhost", "127.0.0.1"]
+        .iter()
+        .any(|name| host.eq_ignore_ascii_case(&format!("{}:{}", name, port)))
+}
+
+/// The link to an audio file, such as /audio/nouns/der%20Apfel.mp3, if it is in the audio
+/// directory
+fn audio_url(audio: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(audio).ok()?;
+    let mut url = String::from("/audio");
+    for component in relative.components() {
+        url.push('/');
+        url.extend(utf8_percent_encode(
+            &component.as_os_str().to_string_lossy(),
+            ESCAPED,
+        ));
+    }
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::types::serve::{audio_url, is_local};
+
+    #[test]
+    fn audio_links() {
+        let audio = Path::new("data/audio");
+        assert_eq!(
+            audio_url(audio, Path::new("data/audio/nouns/der Käse.mp3")).as_deref(),
+            Some("/audio/nouns/der%20K%C3%A4se.mp3")
+        );
+        assert_eq!(audio_url(audio, Path::new("elsewhere/Käse.mp3")), None);
+    }
+
+    #[test]
+    fn local_hosts() {
+        assert!(is_local("localhost:8080", 8080));
+        assert!(is_local("127.0.0.1:8080", 8080));
+        assert!(!is_local("localhost:8081", 8080));
+        assert!(!is_local("attacker.example:8080", 8080));
+        assert!(!is_local("", 8080));
+    }
+}
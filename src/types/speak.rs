@@ -0,0 +1,171 @@
+use std::path::{Path, PathBuf};
+
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+
+use crate::types::audio::{play_file_or_print_error, play_file_slowly_or_print_error, Recording};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::phrases::Phrases;
+use crate::types::progress::History;
+use crate::types::ui::{Input, Ui};
+
+/// A noun or phrase to say, with the recording to compare with
+struct Utterance {
+    /// The noun or the phrase, as recorded in the history
+    item: String,
+    /// What to say, such as der Apfel
+    german: String,
+    english: String,
+    /// Either noun or phrase
+    kind: &'static str,
+    reference: PathBuf,
+}
+
+/// How the answer compares with the reference, as judged by the user
+#[derive(Debug, PartialEq, Eq)]
+enum Grade {
+    Good,
+    Again,
+}
+
+/// Plays the noun or phrase, records the user saying it (or reads the WAV file given with
+/// --input), and plays both so that the user can grade the pronunciation
+pub(crate) fn speak(
+    data: &DataDir,
+    config: &Config,
+    input: Option<&Path>,
+    ui: &mut dyn Ui,
+) -> Result<()> {
+    let mut utterances = utterances(data, config)?;
+    if utterances.is_empty() {
+        return ui.finish(vec!["No nouns or phrases with audio files found".into()]);
+    }
+    utterances.shuffle(&mut thread_rng());
+
+    ui.start("nouns and phrases", utterances.len());
+
+    let (mut good, mut again) = (0, 0);
+    'utterances: for (index, utterance) in utterances.iter().enumerate() {
+        let remaining = utterances.len() - index;
+        ui.show(format!("Say: {} ({})", utterance.german, utterance.english).into());
+        play_file_or_print_error(&utterance.reference);
+
+        let recording = match input {
+            Some(file) => Recording::read(file)?,
+            None => {
+                loop {
+                    match ui.ask(&"Press enter to start recording".into(), remaining)? {
+                        Input::Quit => break 'utterances,
+                        Input::Skip => continue 'utterances,
+                        Input::Slow => play_file_slowly_or_print_error(&utterance.reference),
+                        Input::English | Input::Replay | Input::Answer(_) => break,
+                    }
+                }
+                Recording::record(|| {
+                    ui.ask(&"Recording, press enter to stop".into(), remaining)
+                        .map(|_| ())
+                })?
+            }
+        };
+        if recording.duration() < 0.1 {
+            ui.show("Nothing was recorded".into());
+        }
+
+        let mut replay = true;
+        let grade = loop {
+            if replay {
+                ui.show("Reference".into());
+                play_file_or_print_error(&utterance.reference);
+                ui.show(format!("You ({:.1} seconds)", recording.duration()).into());
+                if let Err(e) = recording.play() {
+                    ui.show(e.to_string().into());
+                }
+            }
+
+            replay = false;
+            match ui.ask(&"Did it sound the same? good or again".into(), remaining)? {
+                Input::Quit => break 'utterances,
+                Input::Skip => continue 'utterances,
+                Input::Replay => replay = true,
+                Input::Slow => play_file_slowly_or_print_error(&utterance.reference),
+                Input::English => ui.show(utterance.english.clone().into()),
+                Input::Answer(answer) => match grade(&answer) {
+                    Some(grade) => break grade,
+                    None => ui.help("good (or g) or again (or a)"),
+                },
+            }
+        };
+
+        let correct = grade == Grade::Good;
+        History::record(data, "speak", &utterance.item, utterance.kind, correct);
+        ui.score(correct);
+        if correct {
+            good += 1;
+        } else {
+            again += 1;
+        }
+    }
+
+    ui.finish(vec![format!(
+        "Finished with {} good and {} to say again",
+        good, again
+    )
+    .into()])
+}
+
+/// The selected nouns (with their article) and phrases that have an audio file
+fn utterances(data: &DataDir, config: &Config) -> Result<Vec<Utterance>> {
+    let selection = Selection::new(data, config)?;
+
+    let mut utterances: Vec<Utterance> = selection
+        .nouns(data)?
+        .into_iter()
+        .map(|noun| Utterance {
+            german: format!("{} {}", noun.article, noun.singular),
+            reference: noun.singular_with_article_file_path(data),
+            item: noun.singular,
+            english: noun.english,
+            kind: "noun",
+        })
+        .collect();
+
+    let phrases = Phrases::read(data)?;
+    utterances.extend(
+        selection
+            .phrases(&phrases)
+            .into_iter()
+            .map(|phrase| Utterance {
+                item: phrase.german.clone(),
+                german: phrase.german.clone(),
+                english: phrase.english.clone(),
+                kind: "phrase",
+                reference: phrase.audio_file_path(data),
+            }),
+    );
+
+    utterances.retain(|utterance| utterance.reference.is_file());
+    Ok(utterances)
+}
+
+fn grade(input: &str) -> Option<Grade> {
+    match input.to_lowercase().as_str() {
+        "good" | "g" | "yes" | "y" => Some(Grade::Good),
+        "again" | "a" | "no" | "n" => Some(Grade::Again),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::speak::{grade, Grade};
+
+    #[test]
+    fn self_grades() {
+        assert_eq!(grade("G"), Some(Grade::Good));
+        assert_eq!(grade("again"), Some(Grade::Again));
+        assert_eq!(grade("maybe"), None);
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::types::alphabet::is_confusion;
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::nouns::Noun;
+use crate::types::phrases::{Phrase, Phrases};
+use crate::types::progress::{Attempt, History};
+use crate::types::tags::Tagged;
+use crate::types::verbs::Verb;
+
+const UNTAGGED: &str = "(untagged)";
+
+/// The number of answers, and how many of them were correct
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Score {
+    correct: usize,
+    total: usize,
+}
+
+impl Score {
+    fn add(&mut self, correct: bool) {
+        self.total += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+}
+
+impl Display for Score {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>5} answers, {:>3}% correct",
+            self.total,
+            self.correct * 100 / self.total.max(1)
+        )
+    }
+}
+
+/// Prints the accuracy of the answers in history.csv, per mode and per tag of the words
+pub(crate) fn stats(data: &DataDir) -> Result<()> {
+    let history = History::read(data)?;
+    if history.attempts().is_empty() {
+        println!("No answers recorded yet");
+        return Ok(());
+    }
+
+    let nouns = Noun::read(data)?;
+    let verbs = Verb::read(data)?;
+    let phrases = Phrases::read(data)?;
+    let phrases: Vec<&Phrase> = phrases.iter().collect();
+
+    let mut modes: BTreeMap<&str, Score> = BTreeMap::new();
+    let mut tags: BTreeMap<String, Score> = BTreeMap::new();
+    for attempt in history.attempts() {
+        /* The letters typed instead of the ones heard are already counted as wrong answers */
+        if is_confusion(&attempt.mode) {
+            continue;
+        }
+
+        modes
+            .entry(attempt.mode.as_str())
+            .or_default()
+            .add(attempt.correct);
+
+        /* The exam results are per section, not per word */
+        if attempt.mode == "exam report" {
+            continue;
+        }
+
+        let mut attempt_tags = tags_of(attempt, &nouns, &verbs, &phrases);
+        if attempt_tags.is_empty() {
+            attempt_tags.push(UNTAGGED);
+        }
+        for tag in attempt_tags {
+            tags.entry(tag.to_lowercase())
+                .or_default()
+                .add(attempt.correct);
+        }
+    }
+
+    println!("------------------------------------------------------------");
+    println!("Per mode");
+    for (mode, score) in modes.iter() {
+        println!("  {:<12} {}", mode, score);
+    }
+    println!("------------------------------------------------------------");
+    println!("Per tag");
+    for (tag, score) in tags.iter() {
+        println!("  {:<12} {}", tag, score);
+    }
+    println!("------------------------------------------------------------");
+
+    Ok(())
+}
+
+/// The tags of the word (or phrase) that was practised
+fn tags_of<'a>(
+    attempt: &Attempt,
+    nouns: &'a [Noun],
+    verbs: &'a [Verb],
+    phrases: &[&'a Phrase],
+) -> Vec<&'a str> {
+    let verb = |infinitive: &str| {
+        verbs
+            .iter()
+            .find(|verb| verb.german == infinitive || verb.infinitive() == infinitive)
+    };
+
+    match attempt.mode.as_str() {
+        "articles" | "plural" | "translation" | "spelling" => nouns
+            .iter()
+            .find(|noun| noun.singular == attempt.item)
+            .map(|noun| noun.tags()),
+        "verbs" => verb(&attempt.item).map(|verb| verb.tags()),
+        /* The made up sentences are not in the phrases, so these count for the verb */
+        "sentences" => match phrases
+            .iter()
+            .find(|phrase| phrase.german == attempt.variant)
+        {
+            Some(phrase) => Some(phrase.tags()),
+            None => verb(&attempt.item).map(|verb| verb.tags()),
+        },
+        /* The variant starts with the section, such as verbs praesens ich */
+        "exam" => match attempt.variant.split(' ').next() {
+            Some("articles" | "plural") => nouns
+                .iter()
+                .find(|noun| noun.singular == attempt.item)
+                .map(|noun| noun.tags()),
+            Some("verbs") => verb(&attempt.item).map(|verb| verb.tags()),
+            _ => None,
+        },
+        "listen" if attempt.variant == "phrase" => phrases
+            .iter()
+            .find(|phrase| phrase.german == attempt.item)
+            .map(|phrase| phrase.tags()),
+        "listen" => nouns
+            .iter()
+            .find(|noun| noun.singular == attempt.item)
+            .map(|noun| noun.tags()),
+        "speak" if attempt.variant == "noun" => nouns
+            .iter()
+            .find(|noun| noun.singular == attempt.item)
+            .map(|noun| noun.tags()),
+        "speak" => phrases
+            .iter()
+            .find(|phrase| phrase.german == attempt.item)
+            .map(|phrase| phrase.tags()),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::nouns::Noun;
+    use crate::types::progress::Attempt;
+    use crate::types::stats::{tags_of, Score};
+
+    #[test]
+    fn score() {
+        let mut score = Score::default();
+        score.add(true);
+        score.add(false);
+        score.add(true);
+        assert_eq!(score.to_string(), "    3 answers,  66% correct");
+        assert_eq!(Score::default().to_string(), "    0 answers,   0% correct");
+    }
+
+    #[test]
+    fn tags_of_noun() {
+        let nouns = vec![Noun {
+            english: "apple".to_string(),
+            article: "der".to_string(),
+            singular: "Apfel".to_string(),
+            plural: Some("Äpfel".to_string()),
+            tags: Some("food fruit".to_string()),
+            level: None,
+        }];
+        let attempt = |mode: &str, item: &str| Attempt {
+            timestamp: 0,
+            mode: mode.to_string(),
+            item: item.to_string(),
+            variant: String::new(),
+            correct: true,
+        };
+
+        assert_eq!(
+            tags_of(&attempt("plural", "Apfel"), &nouns, &[], &[]),
+            vec!["food", "fruit"]
+        );
+        assert!(tags_of(&attempt("articles", "Brot"), &nouns, &[], &[]).is_empty());
+    }
+}
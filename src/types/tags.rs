@@ -0,0 +1,89 @@
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+
+/// The CEFR level of a word or phrase
+#[derive(
+    ValueEnum,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub(crate) enum Level {
+    #[clap(name = "A1")]
+    A1,
+    #[clap(name = "A2")]
+    A2,
+    #[clap(name = "B1")]
+    B1,
+    #[clap(name = "B2")]
+    B2,
+    #[clap(name = "C1")]
+    C1,
+    #[clap(name = "C2")]
+    C2,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let level = match self {
+            Level::A1 => "A1",
+            Level::A2 => "A2",
+            Level::B1 => "B1",
+            Level::B2 => "B2",
+            Level::C1 => "C1",
+            Level::C2 => "C2",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+/// The optional tags and level columns of the word lists.  The tags are separated by spaces,
+/// such as "food fruit".
+pub(crate) trait Tagged {
+    fn tag_column(&self) -> Option<&str>;
+
+    fn level(&self) -> Option<Level>;
+
+    fn tags(&self) -> Vec<&str> {
+        self.tag_column()
+            .map(|tags| tags.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags().iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::tags::{Level, Tagged};
+
+    struct Word(Option<&'static str>);
+
+    impl Tagged for Word {
+        fn tag_column(&self) -> Option<&str> {
+            self.0
+        }
+
+        fn level(&self) -> Option<Level> {
+            None
+        }
+    }
+
+    #[test]
+    fn tags_separated_by_spaces() {
+        assert_eq!(Word(Some("food  fruit")).tags(), vec!["food", "fruit"]);
+        assert!(Word(Some("food fruit")).has_tag("Fruit"));
+        assert!(Word(None).tags().is_empty());
+        assert!(!Word(Some("")).has_tag("food"));
+    }
+}
@@ -0,0 +1,601 @@
+use std::path::PathBuf;
+
+use clap::{Args as ClapArgs, ValueEnum};
+use rand::prelude::SliceRandom;
+use rand::{thread_rng, Rng};
+
+use crate::types::audio::{play_file_or_print_error, play_file_slowly_or_print_error};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::ui::{Message, Ui};
+
+/* The number of times or dates asked when no count is given */
+const DEFAULT_COUNT: usize = 20;
+
+const UNITS: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+
+const TENS: [&str; 6] = ["", "", "zwanzig", "dreißig", "vierzig", "fünfzig"];
+
+const MONTHS: [(&str, u32); 12] = [
+    ("Januar", 31),
+    ("Februar", 28),
+    ("März", 31),
+    ("April", 30),
+    ("Mai", 31),
+    ("Juni", 30),
+    ("Juli", 31),
+    ("August", 31),
+    ("September", 30),
+    ("Oktober", 31),
+    ("November", 30),
+    ("Dezember", 31),
+];
+
+#[derive(ClapArgs, Clone, Debug, Default)]
+pub(crate) struct TimeArgs {
+    /// What to practice, such as clock,date (both when missing)
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub(crate) kind: Vec<Kind>,
+
+    /// The number of times and dates to practice
+    #[clap(long)]
+    pub(crate) count: Option<usize>,
+}
+
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// The time of day, such as halb drei or vierzehn Uhr dreißig
+    #[clap(name = "clock")]
+    Clock,
+    /// The day of the year, such as am dritten Mai
+    #[clap(name = "date")]
+    Date,
+}
+
+/// A time of day (in steps of five minutes) or a day of the year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Moment {
+    Clock { hour: u32, minute: u32 },
+    Date { day: u32, month: u32 },
+}
+
+/// Shows a time or a date in digits to write in words, or the other way around
+pub(crate) fn time(
+    data: &DataDir,
+    config: &Config,
+    options: &TimeArgs,
+    ui: &mut dyn Ui,
+) -> Result<()> {
+    let count = options.count.unwrap_or(DEFAULT_COUNT);
+    let kinds = if options.kind.is_empty() {
+        vec![Kind::Clock, Kind::Date]
+    } else {
+        options.kind.clone()
+    };
+
+    let mut rng = thread_rng();
+    let mut exercises: Vec<Telling> = (0..count)
+        .map(|_| {
+            let moment = match kinds.choose(&mut rng) {
+                Some(Kind::Date) => Moment::random_date(&mut rng),
+                _ => Moment::random_clock(&mut rng),
+            };
+            Telling::new(data, moment, rng.gen(), &mut rng)
+        })
+        .collect();
+    let mut incorrect: Vec<String> = Vec::new();
+
+    ui.start("times and dates", exercises.len());
+
+    while !exercises.is_empty() {
+        let exercise = exercises.remove(0);
+        match attempt(&exercise, data, config, ui, exercises.len() + 1)? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Wrong => {
+                let line = format!(
+                    " - {}: {}",
+                    exercise.moment.digits(),
+                    exercise.moment.words().join(" or ")
+                );
+                if !incorrect.contains(&line) {
+                    incorrect.push(line);
+                }
+                exercises.push(exercise);
+            }
+            Outcome::Correct | Outcome::Skipped => {}
+        }
+    }
+
+    let mut summary = vec![Message::new(format!(
+        "Finished {} times and dates with {} incorrect answers",
+        count,
+        incorrect.len()
+    ))];
+    summary.extend(incorrect.into_iter().map(Message::new));
+    ui.finish(summary)
+}
+
+impl Moment {
+    fn random_clock(rng: &mut impl Rng) -> Self {
+        Moment::Clock {
+            hour: rng.gen_range(0..24),
+            minute: rng.gen_range(0..12) * 5,
+        }
+    }
+
+    fn random_date(rng: &mut impl Rng) -> Self {
+        let month = rng.gen_range(1..=12);
+        Moment::Date {
+            day: rng.gen_range(1..=MONTHS[month as usize - 1].1),
+            month,
+        }
+    }
+
+    /// Such as 14:30 or 3.5.
+    fn digits(&self) -> String {
+        match self {
+            Moment::Clock { hour, minute } => format!("{}:{:02}", hour, minute),
+            Moment::Date { day, month } => format!("{}.{}.", day, month),
+        }
+    }
+
+    /// The ways to say it, the official one first, such as vierzehn Uhr dreißig and halb drei
+    fn words(&self) -> Vec<String> {
+        match *self {
+            Moment::Clock { hour, minute } => {
+                vec![official(hour, minute), colloquial(hour, minute)]
+            }
+            Moment::Date { day, month } => vec![
+                format!("am {} {}", ordinal(day), MONTHS[month as usize - 1].0),
+                format!("am {} {}", ordinal(day), ordinal(month)),
+            ],
+        }
+    }
+
+    /// Other ways to say it that are heard in some regions, such as viertel drei for 2:15
+    fn regional(&self) -> Vec<String> {
+        match *self {
+            Moment::Clock { hour, minute: 15 } => vec![format!("viertel {}", next_hour(hour))],
+            Moment::Clock { hour, minute: 45 } => {
+                vec![format!("dreiviertel {}", next_hour(hour))]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The time or the date typed in digits, such as 14:30, 14.30 or 3.5., if it is one
+    fn parse(text: &str, clock: bool) -> Option<Self> {
+        let text = text.trim().trim_end_matches('.');
+        let (first, second) = text
+            .split_once(':')
+            .or_else(|| text.split_once('.'))
+            .or_else(|| text.split_once(' '))?;
+        let first = first.trim().parse().ok()?;
+        let second = second.trim().parse().ok()?;
+        if clock {
+            Some(Moment::Clock {
+                hour: first,
+                minute: second,
+            })
+        } else {
+            Some(Moment::Date {
+                day: first,
+                month: second,
+            })
+        }
+    }
+
+    /// Whether the time typed is this one, where a colloquial time such as halb drei can be in the
+    /// morning or the afternoon
+    fn matches(&self, other: &Moment, twelve_hours: bool) -> bool {
+        match (self, other) {
+            (
+                Moment::Clock { hour, minute },
+                Moment::Clock {
+                    hour: other_hour,
+                    minute: other_minute,
+                },
+            ) if twelve_hours => hour % 12 == other_hour % 12 && minute == other_minute,
+            _ => self == other,
+        }
+    }
+}
+
+/// A number up to 99 in words, such as einundzwanzig
+fn cardinal(number: u32) -> String {
+    match number {
+        0..=19 => UNITS[number as usize].to_string(),
+        _ if number % 10 == 0 => TENS[number as usize / 10].to_string(),
+        _ => format!(
+            "{}und{}",
+            unit_prefix(number % 10),
+            TENS[number as usize / 10]
+        ),
+    }
+}
+
+/// The unit before a noun or a ten, such as ein Uhr or einundzwanzig
+fn unit_prefix(number: u32) -> String {
+    if number == 1 {
+        "ein".to_string()
+    } else {
+        cardinal(number)
+    }
+}
+
+/// The ordinal after am, such as ersten, dritten or zwanzigsten
+fn ordinal(number: u32) -> String {
+    match number {
+        1 => "ersten".to_string(),
+        3 => "dritten".to_string(),
+        7 => "siebten".to_string(),
+        8 => "achten".to_string(),
+        2..=19 => format!("{}ten", cardinal(number)),
+        _ => format!("{}sten", cardinal(number)),
+    }
+}
+
+/// Such as vierzehn Uhr dreißig or ein Uhr
+fn official(hour: u32, minute: u32) -> String {
+    let hour = format!("{} Uhr", unit_prefix(hour));
+    if minute == 0 {
+        hour
+    } else {
+        format!("{} {}", hour, cardinal(minute))
+    }
+}
+
+/// Such as halb drei, Viertel nach vier or fünf vor halb acht, on a twelve hour clock
+fn colloquial(hour: u32, minute: u32) -> String {
+    let this_hour = twelve_hour(hour);
+    let next = next_hour(hour);
+    match minute {
+        0 => format!("{} Uhr", unit_prefix(this_hour)),
+        15 => format!("Viertel nach {}", cardinal(this_hour)),
+        30 => format!("halb {}", next),
+        45 => format!("Viertel vor {}", next),
+        1..=20 => format!("{} nach {}", cardinal(minute), cardinal(this_hour)),
+        21..=29 => format!("{} vor halb {}", cardinal(30 - minute), next),
+        31..=39 => format!("{} nach halb {}", cardinal(minute - 30), next),
+        _ => format!("{} vor {}", cardinal(60 - minute), next),
+    }
+}
+
+fn twelve_hour(hour: u32) -> u32 {
+    match hour % 12 {
+        0 => 12,
+        hour => hour,
+    }
+}
+
+/// The hour a colloquial time such as halb drei counts towards
+fn next_hour(hour: u32) -> String {
+    cardinal(twelve_hour(hour + 1))
+}
+
+/// Lower case words, for the comparison of the answers
+fn normalise(config: &Config, text: &str) -> String {
+    config.spelling(
+        &text
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" "),
+    )
+}
+
+/// Telling a time or a date, either from the digits to the words or the other way around
+struct Telling {
+    moment: Moment,
+    /// Whether the words are typed, rather than the digits
+    to_words: bool,
+    /// Whether the words shown, when the digits are typed, are the official ones
+    official: bool,
+    /// The recordings of the official time or date, such as 14, Uhr and 30, if all of them are
+    /// recorded
+    clips: Vec<PathBuf>,
+}
+
+impl Telling {
+    fn new(data: &DataDir, moment: Moment, to_words: bool, rng: &mut impl Rng) -> Self {
+        Telling {
+            moment,
+            to_words,
+            official: rng.gen(),
+            clips: clips(data, &moment),
+        }
+    }
+
+    /// The words shown when the digits are typed
+    fn shown(&self) -> String {
+        let mut words = self.moment.words();
+        words.swap_remove(if self.official { 0 } else { 1 })
+    }
+
+    /// The recording matches the words, unless these are colloquial
+    fn audible(&self) -> bool {
+        !self.clips.is_empty() && (self.to_words || self.official)
+    }
+
+    fn play(&self, slow: bool) {
+        for clip in self.clips.iter() {
+            if slow {
+                play_file_slowly_or_print_error(clip);
+            } else {
+                play_file_or_print_error(clip);
+            }
+        }
+    }
+}
+
+/// The text to record for the hours and minutes of the clock (in audio/numbers) and for the words
+/// of the times and dates (in audio/time), such as Uhr, dritten or Mai, with their files
+pub(crate) fn time_recordings(data: &DataDir) -> Vec<(String, PathBuf)> {
+    let numbers = (0..24)
+        .chain((5..60).step_by(5))
+        .map(|number| (cardinal(number), number_file(data, number)));
+    let words = ["Uhr".to_string(), "am".to_string()]
+        .into_iter()
+        .chain((1..=31).map(ordinal))
+        .chain(MONTHS.iter().map(|(month, _)| month.to_string()))
+        .map(|word| {
+            let file = word_file(data, &word);
+            (word, file)
+        });
+    numbers.chain(words).collect()
+}
+
+fn number_file(data: &DataDir, number: u32) -> PathBuf {
+    data.audio("numbers")
+        .join(number.to_string())
+        .with_extension("mp3")
+}
+
+fn word_file(data: &DataDir, word: &str) -> PathBuf {
+    data.audio("time").join(word).with_extension("mp3")
+}
+
+/// The recordings of the official time or date, or none if one of them is missing
+fn clips(data: &DataDir, moment: &Moment) -> Vec<PathBuf> {
+    let clips = clip_files(data, moment);
+    if clips.iter().all(|clip| clip.is_file()) {
+        clips
+    } else {
+        vec![]
+    }
+}
+
+/// The files of the official time or date, such as 14, Uhr and 30 or am, dritten and Mai
+fn clip_files(data: &DataDir, moment: &Moment) -> Vec<PathBuf> {
+    match *moment {
+        Moment::Clock { hour, minute } => {
+            let mut clips = vec![number_file(data, hour), word_file(data, "Uhr")];
+            if minute != 0 {
+                clips.push(number_file(data, minute));
+            }
+            clips
+        }
+        Moment::Date { day, month } => vec![
+            word_file(data, "am"),
+            word_file(data, &ordinal(day)),
+            word_file(data, MONTHS[month as usize - 1].0),
+        ],
+    }
+}
+
+impl Exercise for Telling {
+    fn mode(&self) -> &'static str {
+        "time"
+    }
+
+    fn item(&self) -> String {
+        self.moment.digits()
+    }
+
+    fn variant(&self) -> String {
+        if self.to_words {
+            "in words"
+        } else {
+            "in digits"
+        }
+        .to_string()
+    }
+
+    fn present(&self, _data: &DataDir) -> Message {
+        if self.to_words {
+            format!("Write {} in words", self.moment.digits()).into()
+        } else {
+            if self.audible() {
+                self.play(false);
+            }
+            format!("Write {} in digits", self.shown()).into()
+        }
+    }
+
+    fn expected(&self) -> &'static str {
+        if self.to_words {
+            "the time or the date in words, such as halb drei or am dritten Mai"
+        } else {
+            "the time or the date in digits, such as 14:30 or 3.5."
+        }
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        if self.to_words {
+            let answer = normalise(config, answer);
+            Some(
+                self.moment
+                    .words()
+                    .iter()
+                    .chain(self.moment.regional().iter())
+                    .any(|words| normalise(config, words) == answer),
+            )
+        } else {
+            let clock = matches!(self.moment, Moment::Clock { .. });
+            let typed = Moment::parse(answer, clock)?;
+            Some(self.moment.matches(&typed, !self.official))
+        }
+    }
+
+    fn feedback(
+        &self,
+        _data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let verdict = if correct { "Correct" } else { "Wrong! It was" };
+        ui.show(
+            format!(
+                "{}: {} ({})",
+                verdict,
+                self.moment.digits(),
+                self.moment.words().join(" or ")
+            )
+            .into(),
+        );
+        if self.to_words && self.audible() {
+            self.play(false);
+        }
+    }
+
+    /* The recording would give the words away */
+    fn replay(&self, _data: &DataDir, slow: bool) -> bool {
+        if self.to_words || !self.audible() {
+            return false;
+        }
+        self.play(slow);
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        None
+    }
+
+    fn solution(&self) -> String {
+        if self.to_words {
+            self.moment.words()[0].clone()
+        } else {
+            self.moment.digits()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use crate::types::data::DataDir;
+    use crate::types::time::{cardinal, clip_files, ordinal, time_recordings, Moment};
+
+    #[test]
+    fn numbers_in_words() {
+        assert_eq!(cardinal(0), "null");
+        assert_eq!(cardinal(21), "einundzwanzig");
+        assert_eq!(cardinal(35), "fünfunddreißig");
+        assert_eq!(cardinal(50), "fünfzig");
+        assert_eq!(ordinal(1), "ersten");
+        assert_eq!(ordinal(3), "dritten");
+        assert_eq!(ordinal(19), "neunzehnten");
+        assert_eq!(ordinal(31), "einunddreißigsten");
+    }
+
+    #[test]
+    fn times_and_dates_in_words() {
+        let clock = |hour, minute| Moment::Clock { hour, minute }.words();
+        assert_eq!(clock(14, 30), vec!["vierzehn Uhr dreißig", "halb drei"]);
+        assert_eq!(clock(1, 0), vec!["ein Uhr", "ein Uhr"]);
+        assert_eq!(
+            clock(0, 15),
+            vec!["null Uhr fünfzehn", "Viertel nach zwölf"]
+        );
+        assert_eq!(
+            clock(16, 45),
+            vec!["sechzehn Uhr fünfundvierzig", "Viertel vor fünf"]
+        );
+        assert_eq!(
+            clock(7, 25),
+            vec!["sieben Uhr fünfundzwanzig", "fünf vor halb acht"]
+        );
+        assert_eq!(clock(11, 50), vec!["elf Uhr fünfzig", "zehn vor zwölf"]);
+
+        let date = Moment::Date { day: 3, month: 5 };
+        assert_eq!(date.words(), vec!["am dritten Mai", "am dritten fünften"]);
+        assert_eq!(date.digits(), "3.5.");
+    }
+
+    #[test]
+    fn parse_digits() {
+        let half_past_two = Moment::Clock {
+            hour: 14,
+            minute: 30,
+        };
+        assert_eq!(Moment::parse("14:30", true), Some(half_past_two));
+        assert_eq!(Moment::parse(" 14.30 ", true), Some(half_past_two));
+        assert_eq!(Moment::parse("halb drei", true), None);
+
+        let typed = Moment::parse("2:30", true).unwrap();
+        assert!(half_past_two.matches(&typed, true));
+        assert!(!half_past_two.matches(&typed, false));
+
+        assert_eq!(
+            Moment::parse("03.05.", false),
+            Some(Moment::Date { day: 3, month: 5 })
+        );
+    }
+
+    #[test]
+    fn recordings_cover_the_clips() {
+        let data = DataDir::new("");
+        let recorded: Vec<_> = time_recordings(&data)
+            .into_iter()
+            .map(|(_, file)| file)
+            .collect();
+        for moment in [
+            Moment::Clock {
+                hour: 23,
+                minute: 55,
+            },
+            Moment::Date { day: 31, month: 12 },
+        ] {
+            for clip in clip_files(&data, &moment) {
+                assert!(recorded.contains(&clip), "{}", clip.display());
+            }
+        }
+
+        /* A copy of another clip is a placeholder, not a recording */
+        let mut contents = HashMap::new();
+        for file in recorded.iter().filter(|file| file.is_file()) {
+            let other = contents.insert(fs::read(file).unwrap(), file);
+            /* The minutes such as 5 are also hours */
+            if let Some(other) = other.filter(|other| *other != file) {
+                panic!("{} is a copy of {}", file.display(), other.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use rand::prelude::{IteratorRandom, SliceRandom};
+use rand::thread_rng;
+
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::lint::is_article;
+use crate::types::nouns::Noun;
+use crate::types::ui::{Message, Ui};
+
+/// The German noun for the English one, such as Apfel for apple
+struct Translation<'a> {
+    noun: &'a Noun,
+    /// Other nouns to pick from in the multiple choice, of the same gender where possible
+    others: Vec<&'a Noun>,
+}
+
+pub(crate) fn translation(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let nouns: Vec<Noun> = Selection::new(data, config)?.nouns(data)?;
+    if nouns.is_empty() {
+        return ui.finish(vec!["No nouns found".into()]);
+    }
+
+    /* As in the articles mode, a few nouns picked at random */
+    let mut picked: Vec<&Noun> = nouns
+        .iter()
+        .choose_multiple(&mut thread_rng(), config.articles.count);
+    picked.shuffle(&mut thread_rng());
+
+    let number_of_nouns = picked.len();
+    let mut incorrect: Vec<&Noun> = Vec::new();
+
+    ui.start("translations", number_of_nouns);
+
+    while !picked.is_empty() {
+        let noun = picked.remove(0);
+        match attempt(
+            &Translation::new(noun, &nouns),
+            data,
+            config,
+            ui,
+            picked.len() + 1,
+        )? {
+            Outcome::Quit => return Ok(()),
+            Outcome::Wrong => {
+                if !incorrect.contains(&noun) {
+                    incorrect.push(noun);
+                }
+                picked.push(noun);
+            }
+            Outcome::Correct | Outcome::Skipped => {}
+        }
+    }
+
+    let mut summary = vec![Message::new(format!(
+        "Finished {} translations with {} incorrect answers",
+        number_of_nouns,
+        incorrect.len()
+    ))];
+    summary.extend(incorrect.iter().map(|noun| {
+        Message::new(" - ")
+            .append(noun.with_article())
+            .text(format!(" ({})", noun.english))
+    }));
+    ui.finish(summary)
+}
+
+impl<'a> Translation<'a> {
+    fn new(noun: &'a Noun, nouns: &'a [Noun]) -> Self {
+        let mut rng = thread_rng();
+        let (mut others, rest): (Vec<&Noun>, Vec<&Noun>) = nouns
+            .iter()
+            .filter(|other| other.singular != noun.singular && other.english != noun.english)
+            .partition(|other| other.article == noun.article);
+        others.shuffle(&mut rng);
+        others.extend(rest.choose_multiple(&mut rng, rest.len().min(4)));
+        others.truncate(4);
+        Translation { noun, others }
+    }
+}
+
+impl Exercise for Translation<'_> {
+    fn mode(&self) -> &'static str {
+        "translation"
+    }
+
+    fn item(&self) -> String {
+        self.noun.singular.clone()
+    }
+
+    fn variant(&self) -> String {
+        "german".to_string()
+    }
+
+    /* Playing the noun would give the answer away */
+    fn present(&self, _data: &DataDir) -> Message {
+        format!("{} [ÄÖÜäöüß]", self.noun.english).into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the German noun, with or without its article"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        let answer = answer.trim();
+        let noun = match answer.split_once(' ') {
+            Some((article, noun)) if is_article(&article.to_lowercase()) => noun.trim(),
+            _ => answer,
+        };
+        Some(
+            config.spelling(&noun.to_lowercase())
+                == config.spelling(&self.noun.singular.to_lowercase()),
+        )
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        _config: &Config,
+        ui: &mut dyn Ui,
+        _answer: &str,
+        correct: bool,
+    ) {
+        let verdict = if correct {
+            "Correct answer: "
+        } else {
+            "Wrong! Correct answer: "
+        };
+        ui.show(Message::new(verdict).append(self.noun.with_article()));
+        self.noun.play_singular_with_article(data);
+    }
+
+    fn replay(&self, _data: &DataDir, _slow: bool) -> bool {
+        false
+    }
+
+    fn english(&self) -> Option<String> {
+        None
+    }
+
+    fn solution(&self) -> String {
+        format!("{} {}", self.noun.article, self.noun.singular)
+    }
+
+    fn choices(&self) -> Vec<String> {
+        let mut choices = vec![self.noun.singular.clone()];
+        choices.extend(self.others.iter().map(|other| other.singular.clone()));
+        choices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::config::Config;
+    use crate::types::exercise::Exercise;
+    use crate::types::nouns::Noun;
+    use crate::types::translation::Translation;
+
+    #[test]
+    fn noun_for_english() {
+        let config = Config::default();
+        let nouns = vec![
+            Noun::example("apple", "der", "Apfel", "Äpfel"),
+            Noun::example("pear", "die", "Birne", "Birnen"),
+            Noun::example("house", "das", "Haus", "Häuser"),
+            Noun::example("dog", "der", "Hund", "Hunde"),
+            Noun::example("spoon", "der", "Löffel", "Löffel"),
+            Noun::example("chair", "der", "Stuhl", "Stühle"),
+            Noun::example("table", "der", "Tisch", "Tische"),
+        ];
+        let exercise = Translation::new(&nouns[0], &nouns);
+
+        assert_eq!(exercise.check(&config, "apfel"), Some(true));
+        assert_eq!(exercise.check(&config, "der Apfel"), Some(true));
+        assert_eq!(exercise.check(&config, "Birne"), Some(false));
+
+        let choices = exercise.choices();
+        assert_eq!(choices[0], "Apfel");
+        assert_eq!(choices.len(), 5);
+        assert!(exercise.others.iter().all(|other| other.article == "der"));
+    }
+}
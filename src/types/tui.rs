@@ -0,0 +1,306 @@
+use std::io::{stdout, Stdout};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph};
+use ratatui::Terminal;
+
+use crate::types::error::{Error, Result};
+use crate::types::ui::{Colour, Input, Message, Ui};
+
+const KEYS: &str =
+    "Enter: answer (blank: replay) | Ctrl-R: replay | Ctrl-S: slower | Ctrl-E: English | Tab: skip | Esc: quit";
+
+/// The full screen interface, with a progress bar for the items left and the score so far
+pub(crate) struct Tui<B: Backend> {
+    terminal: Terminal<B>,
+    /// Whether the terminal was switched to raw mode, and has to be restored
+    raw: bool,
+    colours: bool,
+    title: String,
+    total: usize,
+    remaining: usize,
+    correct: usize,
+    answered: usize,
+    lines: Vec<Message>,
+    prompt: Message,
+}
+
+impl Tui<CrosstermBackend<Stdout>> {
+    pub(crate) fn new(colours: bool) -> Result<Self> {
+        enable_raw_mode().map_err(Error::Terminal)?;
+        /* There is no Tui yet to restore the terminal when dropped, if these fail */
+        let terminal = execute!(stdout(), EnterAlternateScreen)
+            .and_then(|_| Terminal::new(CrosstermBackend::new(stdout())))
+            .map_err(|e| {
+                let _ = execute!(stdout(), LeaveAlternateScreen);
+                let _ = disable_raw_mode();
+                Error::Terminal(e)
+            })?;
+
+        let mut tui = Tui::with_terminal(terminal, colours);
+        tui.raw = true;
+        Ok(tui)
+    }
+}
+
+impl<B: Backend> Tui<B> {
+    fn with_terminal(terminal: Terminal<B>, colours: bool) -> Self {
+        Tui {
+            terminal,
+            raw: false,
+            colours,
+            title: String::new(),
+            total: 0,
+            remaining: 0,
+            correct: 0,
+            answered: 0,
+            lines: vec![],
+            prompt: Message::default(),
+        }
+    }
+
+    fn draw(&mut self, input: &str) -> Result<()> {
+        let line = |message: &Message| -> Line<'static> {
+            Line::from(
+                message
+                    .parts
+                    .iter()
+                    .map(|(text, colour)| match colour {
+                        Some(colour) if self.colours => {
+                            Span::styled(text.clone(), Style::default().fg(colour_of(*colour)))
+                        }
+                        _ => Span::raw(text.clone()),
+                    })
+                    .collect::<Vec<Span>>(),
+            )
+        };
+
+        let done = self.total.saturating_sub(self.remaining);
+        let ratio = if self.total == 0 {
+            0.0
+        } else {
+            (done as f64 / self.total as f64).clamp(0.0, 1.0)
+        };
+        let score = match self.answered {
+            0 => "Score: no answers yet".to_string(),
+            answered => format!(
+                "Score: {} of {} correct ({}%)",
+                self.correct,
+                answered,
+                self.correct * 100 / answered
+            ),
+        };
+        let lines: Vec<Line> = self.lines.iter().map(line).collect();
+        let mut prompt = line(&self.prompt);
+        prompt.spans.push(Span::raw(format!(": {}", input)));
+        let cursor = prompt.width() as u16;
+
+        self.terminal
+            .draw(|frame| {
+                let [progress, score_area, log, answer, keys] = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                    Constraint::Min(3),
+                    Constraint::Length(3),
+                    Constraint::Length(1),
+                ])
+                .areas(frame.size());
+
+                frame.render_widget(
+                    Gauge::default()
+                        .block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .title(self.title.as_str()),
+                        )
+                        .gauge_style(Style::default().fg(Color::Cyan))
+                        .ratio(ratio)
+                        .label(format!("{} of {} left", self.remaining, self.total)),
+                    progress,
+                );
+                frame.render_widget(Paragraph::new(score), score_area);
+
+                /* The latest lines that fit */
+                let height = log.height.saturating_sub(2) as usize;
+                let skip = lines.len().saturating_sub(height);
+                frame.render_widget(
+                    Paragraph::new(lines[skip..].to_vec())
+                        .block(Block::default().borders(Borders::ALL)),
+                    log,
+                );
+
+                frame.render_widget(
+                    Paragraph::new(prompt)
+                        .block(Block::default().borders(Borders::ALL).title("Answer")),
+                    answer,
+                );
+                frame.set_cursor(
+                    (answer.x + 1 + cursor).min(answer.right().saturating_sub(2)),
+                    answer.y + 1,
+                );
+                frame.render_widget(
+                    Paragraph::new(KEYS).style(Style::default().fg(Color::DarkGray)),
+                    keys,
+                );
+            })
+            .map_err(Error::Terminal)?;
+        Ok(())
+    }
+}
+
+impl<B: Backend> Ui for Tui<B> {
+    fn start(&mut self, title: &str, items: usize) {
+        self.title = format!(" {} {} ", items, title);
+        self.total = items;
+        self.remaining = items;
+        self.lines.clear();
+    }
+
+    fn ask(&mut self, prompt: &Message, remaining: usize) -> Result<Input> {
+        self.prompt = prompt.clone();
+        self.remaining = remaining;
+
+        /* Anything printed outside of the interface, such as a missing audio file, is wiped */
+        self.terminal.clear().map_err(Error::Terminal)?;
+
+        let mut input = String::new();
+        loop {
+            self.draw(&input)?;
+
+            let Event::Key(key) = event::read().map_err(Error::Terminal)? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let control = key.modifiers.contains(KeyModifiers::CONTROL);
+            return Ok(match key.code {
+                KeyCode::Esc => Input::Quit,
+                KeyCode::Char('c') if control => Input::Quit,
+                KeyCode::Char('r') if control => Input::Replay,
+                KeyCode::Char('s') if control => Input::Slow,
+                KeyCode::Char('e') if control => Input::English,
+                KeyCode::Tab => Input::Skip,
+                KeyCode::Enter => {
+                    let input = Input::parse(&input);
+                    if let Input::Answer(answer) = &input {
+                        self.lines
+                            .push(prompt.clone().text(format!(": {}", answer)));
+                    }
+                    input
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                    continue;
+                }
+                KeyCode::Char(c) if !control => {
+                    input.push(c);
+                    continue;
+                }
+                _ => continue,
+            });
+        }
+    }
+
+    fn show(&mut self, message: Message) {
+        self.lines.push(message);
+        /* Shown right away, as the audio may play before the next question */
+        if let Err(e) = self.draw("") {
+            self.lines.push(Message::new(e.to_string()));
+        }
+    }
+
+    fn score(&mut self, correct: bool) {
+        self.answered += 1;
+        self.correct += correct as usize;
+    }
+
+    fn help(&mut self, expected: &str) {
+        self.show(Message::new(format!(
+            "Expected {} (or one of the keys below)",
+            expected
+        )));
+    }
+
+    fn finish(&mut self, summary: Vec<Message>) -> Result<()> {
+        self.remaining = 0;
+        self.lines.extend(summary);
+        self.prompt = Message::new("Press any key to close");
+        self.draw("")?;
+
+        loop {
+            if let Event::Key(key) = event::read().map_err(Error::Terminal)? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+impl<B: Backend> Drop for Tui<B> {
+    fn drop(&mut self) {
+        if !self.raw {
+            return;
+        }
+
+        /* Nothing more can be done if the terminal cannot be restored */
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), LeaveAlternateScreen);
+        let _ = self.terminal.show_cursor();
+    }
+}
+
+fn colour_of(colour: Colour) -> Color {
+    match colour {
+        Colour::Blue => Color::LightBlue,
+        Colour::Red => Color::LightRed,
+        Colour::Green => Color::Green,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use crate::types::tui::Tui;
+    use crate::types::ui::{Colour, Message, Ui};
+
+    #[test]
+    fn draw_progress_and_score() {
+        let terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        let mut tui = Tui::with_terminal(terminal, true);
+        tui.start("nouns", 4);
+        tui.remaining = 3;
+        tui.score(true);
+        tui.score(false);
+        tui.show(
+            Message::new("Correct answer: ")
+                .coloured("der", Some(Colour::Blue))
+                .text(" Apfel"),
+        );
+
+        let screen: String = tui
+            .terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("4 nouns"));
+        assert!(screen.contains("3 of 4 left"));
+        assert!(screen.contains("Score: 1 of 2 correct (50%)"));
+        assert!(screen.contains("Correct answer: der Apfel"));
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::{Display, Formatter};
+use std::io::{stdin, stdout, IsTerminal};
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::types::config::Config;
+use crate::types::error::Result;
+use crate::types::protocol::{Jsonl, Protocol, Remote};
+use crate::types::tui::Tui;
+use crate::types::utils::read_line;
+
+/// What the user did when asked for an answer in one of the drills
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Input {
+    /// An answer to check
+    Answer(String),
+    /// Play the audio again
+    Replay,
+    /// Play the audio again, slower
+    Slow,
+    /// Show the English translation
+    English,
+    /// Go to the next item without answering
+    Skip,
+    /// Stop the drill
+    Quit,
+}
+
+/// The colours of the articles, as in the articles mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Colour {
+    Blue,
+    Red,
+    Green,
+}
+
+/// A line of output made of parts, some of which may be coloured (such as the article)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Message {
+    pub(crate) parts: Vec<(String, Option<Colour>)>,
+}
+
+/// How the drills ask for the answers and show the results: line by line (with --plain, or when
+/// not run in a terminal) or on a full screen with a progress bar and the score
+pub(crate) trait Ui {
+    /// Starts a drill with the number of items to practice, such as ("nouns", 25)
+    fn start(&mut self, title: &str, items: usize);
+
+    /// Names the item asked next, such as Apfel/article, for the interfaces that pass it on
+    fn item(&mut self, _id: &str) {}
+
+    /// Asks for the answer, with the number of items left (including this one)
+    fn ask(&mut self, prompt: &Message, remaining: usize) -> Result<Input>;
+
+    /// Shows a line, such as the correct answer
+    fn show(&mut self, message: Message);
+
+    /// Counts an answer in the score
+    fn score(&mut self, correct: bool);
+
+    /// Explains what answer was expected, such as "the articles der, die or das", and how to
+    /// replay, skip or quit
+    fn help(&mut self, expected: &str);
+
+    /// Ends the drill with a summary
+    fn finish(&mut self, summary: Vec<Message>) -> Result<()>;
+}
+
+/// The JSON lines with --protocol jsonl, otherwise the full screen interface unless --plain is given
+/// (or plain is set in the config file), or the input or output is not a terminal
+pub(crate) fn new_ui(config: &Config, protocol: Option<Protocol>) -> Result<Box<dyn Ui>> {
+    if let Some(Protocol::Jsonl) = protocol {
+        return Ok(Box::new(Remote::new(Jsonl, |file: &Path| {
+            Some(file.display().to_string())
+        })));
+    }
+    if config.plain || !stdin().is_terminal() || !stdout().is_terminal() {
+        return Ok(Box::new(Plain));
+    }
+    Ok(Box::new(Tui::new(config.colours)?))
+}
+
+impl Input {
+    /// The typed commands, which work in both interfaces, or else the answer
+    pub(crate) fn parse(input: &str) -> Self {
+        match input.trim().to_lowercase().as_str() {
+            "quit" | "exit" => Input::Quit,
+            "" | "repeat" | "replay" => Input::Replay,
+            "slow" => Input::Slow,
+            "en" | "eng" | "english" => Input::English,
+            "skip" => Input::Skip,
+            _ => Input::Answer(input.trim().to_string()),
+        }
+    }
+}
+
+impl Message {
+    pub(crate) fn new(text: impl Into<String>) -> Self {
+        Message {
+            parts: vec![(text.into(), None)],
+        }
+    }
+
+    /// Appends the text
+    pub(crate) fn text(mut self, text: impl Into<String>) -> Self {
+        self.parts.push((text.into(), None));
+        self
+    }
+
+    /// Appends the text in the colour, if any
+    pub(crate) fn coloured(mut self, text: impl Into<String>, colour: Option<Colour>) -> Self {
+        self.parts.push((text.into(), colour));
+        self
+    }
+
+    /// Appends the parts of the other message
+    pub(crate) fn append(mut self, other: Message) -> Self {
+        self.parts.extend(other.parts);
+        self
+    }
+
+    /// The text without the colours
+    pub(crate) fn plain_text(&self) -> String {
+        self.parts.iter().map(|(text, _)| text.as_str()).collect()
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Message::new(text)
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message::new(text)
+    }
+}
+
+/* The colours are left out with --no-colours, as colored is then turned off */
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (text, colour) in self.parts.iter() {
+            match colour {
+                Some(Colour::Blue) => write!(f, "{}", text.bright_blue())?,
+                Some(Colour::Red) => write!(f, "{}", text.bright_red())?,
+                Some(Colour::Green) => write!(f, "{}", text.green())?,
+                None => write!(f, "{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The line based interface, with the typed commands (such as quit) instead of key bindings
+pub(crate) struct Plain;
+
+impl Ui for Plain {
+    fn start(&mut self, title: &str, items: usize) {
+        println!("------------------------------------------------------------");
+        println!("Loaded {} {}", items, title);
+        println!("------------------------------------------------------------");
+    }
+
+    fn ask(&mut self, prompt: &Message, remaining: usize) -> Result<Input> {
+        Ok(Input::parse(&read_line(&format!(
+            "{:>3} | {}",
+            remaining, prompt
+        ))?))
+    }
+
+    fn show(&mut self, message: Message) {
+        println!("{}", message);
+    }
+
+    fn score(&mut self, _correct: bool) {}
+
+    fn help(&mut self, expected: &str) {
+        println!("Expected {}", expected);
+        println!("         quit or exit: to quit");
+        println!("         (blank) or repeat: to replay the audio");
+        println!("         slow: to replay the audio slower");
+        println!("         en, eng, or english: to show the english translation");
+        println!("         skip: to go to the next one");
+    }
+
+    fn finish(&mut self, summary: Vec<Message>) -> Result<()> {
+        println!("------------------------------------------------------------");
+        for message in summary {
+            println!("{}", message);
+        }
+        println!("------------------------------------------------------------");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::ui::{Colour, Input, Message};
+
+    #[test]
+    fn typed_commands() {
+        assert_eq!(Input::parse(" Quit"), Input::Quit);
+        assert_eq!(Input::parse(""), Input::Replay);
+        assert_eq!(Input::parse("eng"), Input::English);
+        assert_eq!(Input::parse("Äpfel "), Input::Answer("Äpfel".to_string()));
+    }
+
+    #[test]
+    fn message_parts() {
+        let message = Message::new("Correct answer: ")
+            .coloured("der", Some(Colour::Blue))
+            .text(" Apfel");
+        assert_eq!(message.plain_text(), "Correct answer: der Apfel");
+        assert_eq!(message.parts.len(), 3);
+        assert_eq!(message.parts[1], ("der".to_string(), Some(Colour::Blue)));
+    }
+}
@@ -2,8 +2,9 @@ use std::io::{stdin, stdout, Write};
 use std::path::Path;
 
 use rand::Rng;
+use serde::de::DeserializeOwned;
 
-use crate::types::audio::play_file_or_print_error;
+use crate::types::error::{Error, Result};
 
 pub(crate) fn remove_random<T>(vec: &mut Vec<T>) -> T {
     let mut rng = rand::thread_rng();
@@ -11,22 +12,50 @@ pub(crate) fn remove_random<T>(vec: &mut Vec<T>) -> T {
     vec.remove(index)
 }
 
-pub(crate) fn read_line(prompt: &str) -> String {
+pub(crate) fn read_line(prompt: &str) -> Result<String> {
     print!("{}: ", prompt);
-    stdout().flush().unwrap();
+    stdout().flush().map_err(Error::Input)?;
 
-    let mut input = String::new();
-    stdin().read_line(&mut input).expect("Failed to user input");
-    input.trim().to_string()
+    read_input()
 }
 
-pub(crate) fn play_and_read_line(prompt: &str, file_path: &Path) -> String {
-    print!("{}: ", prompt);
-    stdout().flush().unwrap();
+pub(crate) fn read_input() -> Result<String> {
+    let mut input = String::new();
+    stdin().read_line(&mut input).map_err(Error::Input)?;
+    Ok(input.trim().to_string())
+}
 
-    play_file_or_print_error(file_path);
+/// Spells out the umlauts and the ß as they are typed without a German keyboard (Äpfel becomes
+/// Aepfel)
+pub(crate) fn spell_out_umlauts(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ä' => "ae".to_string(),
+            'ö' => "oe".to_string(),
+            'ü' => "ue".to_string(),
+            'Ä' => "Ae".to_string(),
+            'Ö' => "Oe".to_string(),
+            'Ü' => "Ue".to_string(),
+            'ß' => "ss".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
 
-    let mut input = String::new();
-    stdin().read_line(&mut input).expect("Failed to user input");
-    input.trim().to_string()
+/// Reads all rows of a CSV file, together with the line number of each row
+pub(crate) fn read_csv<T: DeserializeOwned>(file: &Path) -> Result<Vec<(u64, T)>> {
+    let mut reader = csv::Reader::from_path(file).map_err(|e| Error::csv(file, &e))?;
+    let headers = reader.headers().map_err(|e| Error::csv(file, &e))?.clone();
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| Error::csv(file, &e))?;
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let row = record
+                .deserialize(Some(&headers))
+                .map_err(|e| Error::csv(file, &e))?;
+            Ok((line, row))
+        })
+        .collect()
 }
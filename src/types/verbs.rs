@@ -1,43 +1,78 @@
 use std::fmt::{Display, Formatter};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::slice::Iter;
 
-use crate::types::audio::play_file_or_print_error;
-use crate::types::utils::{read_line, remove_random};
+use clap::ValueEnum;
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
 
-pub(crate) fn verbs() {
-    let mut verbs = Verb::read();
+use crate::types::audio::{play_file_or_print_error, play_file_slowly_or_print_error};
+use crate::types::config::Config;
+use crate::types::data::DataDir;
+use crate::types::deck::Selection;
+use crate::types::error::Result;
+use crate::types::exercise::{attempt, Exercise, Outcome};
+use crate::types::tags::{Level, Tagged};
+use crate::types::ui::{Message, Ui};
+use crate::types::utils::{read_csv, remove_random};
+
+pub(crate) const VERBS_FILE: &str = "verbs.csv";
+
+pub(crate) fn verbs(data: &DataDir, config: &Config, ui: &mut dyn Ui) -> Result<()> {
+    let tenses = &config.verbs.tenses;
+    let names = tenses
+        .iter()
+        .map(|tense| tense.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut verbs = Selection::new(data, config)?.verbs(data)?;
+    /* Older rows may only have the present tense columns */
+    verbs.retain(|verb| tenses.iter().any(|tense| verb.has_tense(tense)));
     if verbs.is_empty() {
-        println!("No verbs found");
-        return;
+        return ui.finish(vec![format!("No verbs found (with {} forms)", names).into()]);
     }
 
-    println!("----------------------------------------");
-    println!("Loaded {} verbs", verbs.len());
-    println!("----------------------------------------");
+    ui.start(&format!("verbs ({})", names), verbs.len());
 
     loop {
         let verb = remove_random(&mut verbs);
         let mut repeat_verb = false;
 
-        println!("{} ({}): ", verb.infinitive(), verb.english);
-        verb.play_infinitive();
-
-        for pronoun in Pronoun::iter() {
-            let input = &read_line(&format!("{}", pronoun)).to_lowercase();
-            match input.as_str() {
-                "quit" | "exit" => return,
-                input => {
-                    let conjugation = verb.conjugation(pronoun);
-                    if conjugation != input {
-                        println!(
-                            "Wrong! Correct answer is {}",
-                            verb.pronoun_conjugation(pronoun)
-                        );
-                        repeat_verb = true;
-                    };
-                    verb.play_conjugation(pronoun);
-                }
+        /* Each verb is practised in one of the chosen tenses that it has */
+        let tense = *tenses
+            .iter()
+            .filter(|tense| verb.has_tense(tense))
+            .collect::<Vec<&Tense>>()
+            .choose(&mut thread_rng())
+            .unwrap();
+
+        if tenses.len() > 1 {
+            ui.show(format!("{} ({}) in {}: ", verb.infinitive(), verb.english, tense).into());
+        } else {
+            ui.show(format!("{} ({}): ", verb.infinitive(), verb.english).into());
+        }
+        verb.play_infinitive(data);
+
+        let pronouns = if config.verbs.distinct {
+            tense.distinct_pronouns()
+        } else {
+            tense.pronouns().collect()
+        };
+
+        for pronoun in pronouns {
+            let exercise = Conjugation {
+                verb: &verb,
+                tense,
+                pronoun,
+                standalone: false,
+            };
+            match attempt(&exercise, data, config, ui, verbs.len() + 1)? {
+                Outcome::Quit => return Ok(()),
+                /* Skipping moves on to the next verb */
+                Outcome::Skipped => break,
+                Outcome::Wrong => repeat_verb = true,
+                Outcome::Correct => {}
             }
         }
 
@@ -47,18 +82,65 @@ pub(crate) fn verbs() {
             break;
         }
     }
+
+    ui.finish(vec!["Finished the verbs".into()])
 }
 
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub(crate) struct Verb {
-    english: String,
-    german: String,
+    pub(crate) english: String,
+    pub(crate) german: String,
     ich: String,
     du: String,
     er: String,
     wir: String,
     ihr: String,
     sie: String,
+    praeteritum_ich: Option<String>,
+    praeteritum_du: Option<String>,
+    praeteritum_er: Option<String>,
+    praeteritum_wir: Option<String>,
+    praeteritum_ihr: Option<String>,
+    praeteritum_sie: Option<String>,
+    /// Either haben or sein, used to form the Perfekt
+    auxiliary: Option<String>,
+    /// The Partizip II, such as gearbeitet
+    partizip: Option<String>,
+    imperativ_du: Option<String>,
+    imperativ_ihr: Option<String>,
+    imperativ_sie: Option<String>,
+    /// The separable prefix, such as an in anrufen.  The conjugation columns hold the verb without
+    /// it (rufe), as the prefix moves to the end (ich rufe an).
+    prefix: Option<String>,
+    /// Whether the verb takes a reflexive pronoun (ich wasche mich)
+    reflexive: Option<bool>,
+    tags: Option<String>,
+    level: Option<Level>,
+}
+
+/// The conjugation of a verb in a tense, for a pronoun
+pub(crate) struct Conjugation<'a> {
+    verb: &'a Verb,
+    tense: &'a Tense,
+    pronoun: &'static Pronoun,
+    /// Whether the verb and the tense are part of the question, as they are not when all the
+    /// pronouns of a verb are asked one after the other
+    standalone: bool,
+}
+
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Tense {
+    #[clap(name = "praesens")]
+    Praesens,
+    #[clap(name = "praeteritum")]
+    Praeteritum,
+    #[clap(name = "perfekt")]
+    Perfekt,
+    #[clap(name = "futur")]
+    Futur,
+    #[clap(name = "imperativ")]
+    Imperativ,
 }
 
 pub(crate) enum Pronoun {
@@ -76,63 +158,385 @@ pub(crate) enum Pronoun {
 }
 
 impl Verb {
-    pub(crate) fn read() -> Vec<Verb> {
-        let reader = csv::Reader::from_path("verbs.csv");
-
-        reader
-            .expect("Failed to read verbs")
-            .deserialize()
-            .map(|r| r.unwrap())
-            .collect()
+    pub(crate) fn read(data: &DataDir) -> Result<Vec<Verb>> {
+        Ok(read_csv(&data.file(VERBS_FILE))?
+            .into_iter()
+            .map(|(_, verb)| verb)
+            .collect())
     }
 
     pub(crate) fn infinitive(&self) -> String {
-        self.german.clone()
+        if self.is_reflexive() {
+            format!("sich {}", self.german)
+        } else {
+            self.german.clone()
+        }
+    }
+
+    pub(crate) fn has_tense(&self, tense: &Tense) -> bool {
+        tense
+            .pronouns()
+            .all(|pronoun| self.conjugation(pronoun, tense).is_some())
+    }
+
+    /// The conjugated verb without the pronoun, or `None` if the CSV file has no forms for the
+    /// given tense (or the tense has no form for the given pronoun, as with the Imperativ).
+    pub(crate) fn conjugation(&self, pronoun: &Pronoun, tense: &Tense) -> Option<String> {
+        let reflexive = self.reflexive_pronoun(pronoun);
+        let prefix = self.prefix.as_deref();
+
+        /* The finite verb comes first, followed by the reflexive pronoun and then the separable
+        prefix or the non-finite part of the compound tenses */
+        let words = match tense {
+            Tense::Praesens => [Some(self.present(pronoun).as_str()), reflexive, prefix],
+            Tense::Praeteritum => [self.praeteritum(pronoun).as_deref(), reflexive, prefix],
+            Tense::Perfekt => {
+                let auxiliary = match self.auxiliary.as_deref() {
+                    Some("haben") => HABEN.conjugate(pronoun),
+                    Some("sein") => SEIN.conjugate(pronoun),
+                    _ => return None,
+                };
+                [Some(auxiliary), reflexive, self.partizip.as_deref()]
+            }
+            Tense::Futur => [
+                Some(WERDEN.conjugate(pronoun)),
+                reflexive,
+                Some(self.german.as_str()),
+            ],
+            Tense::Imperativ => {
+                let imperativ = match pronoun {
+                    Pronoun::Du => self.imperativ_du.as_deref(),
+                    Pronoun::Ihr => self.imperativ_ihr.as_deref(),
+                    Pronoun::SieFormal | Pronoun::SiePluralFormal => self.imperativ_sie.as_deref(),
+                    _ => None,
+                };
+                [imperativ, reflexive, prefix]
+            }
+        };
+
+        /* The finite verb (and the Partizip II of the Perfekt) may be missing from older rows */
+        words[0]?;
+        if *tense == Tense::Perfekt {
+            words[2]?;
+        }
+
+        Some(words.into_iter().flatten().collect::<Vec<&str>>().join(" "))
+    }
+
+    fn is_reflexive(&self) -> bool {
+        self.reflexive.unwrap_or(false)
+    }
+
+    fn reflexive_pronoun(&self, pronoun: &Pronoun) -> Option<&'static str> {
+        if !self.is_reflexive() {
+            return None;
+        }
+
+        Some(match pronoun {
+            Pronoun::Ich => "mich",
+            Pronoun::Du => "dich",
+            Pronoun::Wir => "uns",
+            Pronoun::Ihr => "euch",
+            _ => "sich",
+        })
+    }
+
+    /// The conjugated verb with the pronoun, as it is spoken in the audio files
+    pub(crate) fn pronoun_conjugation(&self, pronoun: &Pronoun, tense: &Tense) -> Option<String> {
+        let conjugation = self.conjugation(pronoun, tense)?;
+        match tense {
+            /* The Sie form already includes its pronoun (arbeiten Sie) */
+            Tense::Imperativ => Some(format!("{}!", conjugation)),
+            _ => Some(format!("{} {}", pronoun, conjugation)),
+        }
     }
 
-    pub(crate) fn conjugation(&self, pronoun: &Pronoun) -> String {
+    fn present(&self, pronoun: &Pronoun) -> &String {
         match pronoun {
-            Pronoun::Ich => self.ich.clone(),
-            Pronoun::Du => self.du.clone(),
-            Pronoun::SieFormal => self.sie.clone(),
-            Pronoun::Er => self.er.clone(),
-            Pronoun::Sie => self.er.clone(),
-            Pronoun::Es => self.er.clone(),
-            Pronoun::Man => self.er.clone(),
-            Pronoun::Wir => self.wir.clone(),
-            Pronoun::Ihr => self.ihr.clone(),
-            Pronoun::SiePluralFormal => self.sie.clone(),
-            Pronoun::SiePlural => self.sie.clone(),
+            Pronoun::Ich => &self.ich,
+            Pronoun::Du => &self.du,
+            Pronoun::Er | Pronoun::Sie | Pronoun::Es | Pronoun::Man => &self.er,
+            Pronoun::Wir => &self.wir,
+            Pronoun::Ihr => &self.ihr,
+            Pronoun::SieFormal | Pronoun::SiePluralFormal | Pronoun::SiePlural => &self.sie,
         }
     }
 
-    pub(crate) fn pronoun_conjugation(&self, pronoun: &Pronoun) -> String {
-        format!("{} {}", pronoun, self.conjugation(pronoun))
+    fn praeteritum(&self, pronoun: &Pronoun) -> &Option<String> {
+        match pronoun {
+            Pronoun::Ich => &self.praeteritum_ich,
+            Pronoun::Du => &self.praeteritum_du,
+            Pronoun::Er | Pronoun::Sie | Pronoun::Es | Pronoun::Man => &self.praeteritum_er,
+            Pronoun::Wir => &self.praeteritum_wir,
+            Pronoun::Ihr => &self.praeteritum_ihr,
+            Pronoun::SieFormal | Pronoun::SiePluralFormal | Pronoun::SiePlural => {
+                &self.praeteritum_sie
+            }
+        }
     }
 
-    fn play_infinitive(&self) {
-        play_file_or_print_error(&self.infinitive_audio_file_path());
+    fn play_infinitive(&self, data: &DataDir) {
+        play_file_or_print_error(&self.infinitive_audio_file_path(data));
     }
 
-    fn play_conjugation(&self, pronoun: &Pronoun) {
-        play_file_or_print_error(&self.conjugation_audio_file_path(&pronoun));
+    pub(crate) fn play_conjugation(&self, data: &DataDir, pronoun: &Pronoun, tense: &Tense) {
+        if let Some(file) = self.conjugation_audio_file_path(data, pronoun, tense) {
+            play_file_or_print_error(&file);
+        }
     }
 
-    pub(crate) fn infinitive_audio_file_path(&self) -> PathBuf {
-        Self::audio_file_path(&self.infinitive())
+    pub(crate) fn infinitive_audio_file_path(&self, data: &DataDir) -> PathBuf {
+        Self::audio_file_path(data, &self.infinitive())
     }
 
-    pub(crate) fn conjugation_audio_file_path(&self, pronoun: &Pronoun) -> PathBuf {
-        Self::audio_file_path(&self.pronoun_conjugation(&pronoun))
+    pub(crate) fn conjugation_audio_file_path(
+        &self,
+        data: &DataDir,
+        pronoun: &Pronoun,
+        tense: &Tense,
+    ) -> Option<PathBuf> {
+        self.pronoun_conjugation(pronoun, tense)
+            .map(|conjugation| Self::audio_file_path(data, &conjugation))
     }
 
-    fn audio_file_path(file_name_without_extension: &str) -> PathBuf {
-        Path::new("audio/verbs")
+    fn audio_file_path(data: &DataDir, file_name_without_extension: &str) -> PathBuf {
+        data.audio("verbs")
             .join(file_name_without_extension)
             .with_extension("mp3")
     }
 }
 
+impl<'a> Conjugation<'a> {
+    /// The question names the verb and the tense, such as ich ___ (wohnen, Präsens)
+    pub(crate) fn standalone(verb: &'a Verb, tense: &'a Tense, pronoun: &'static Pronoun) -> Self {
+        Conjugation {
+            verb,
+            tense,
+            pronoun,
+            standalone: true,
+        }
+    }
+
+    fn answer(&self, config: &Config, answer: &str) -> Option<Answer> {
+        let conjugation = self.verb.conjugation(self.pronoun, self.tense)?;
+        Some(Answer::check(
+            &config.spelling(&conjugation.to_lowercase()),
+            &config.spelling(&answer.to_lowercase()),
+        ))
+    }
+}
+
+impl Exercise for Conjugation<'_> {
+    fn mode(&self) -> &'static str {
+        "verbs"
+    }
+
+    fn item(&self) -> String {
+        self.verb.german.clone()
+    }
+
+    fn variant(&self) -> String {
+        format!("{} {}", self.tense.name(), self.pronoun.name())
+    }
+
+    fn present(&self, data: &DataDir) -> Message {
+        if !self.standalone {
+            return self.pronoun.to_string().into();
+        }
+
+        self.verb.play_infinitive(data);
+        format!(
+            "{} ___ ({}, {})",
+            self.pronoun,
+            self.verb.infinitive(),
+            self.tense
+        )
+        .into()
+    }
+
+    fn expected(&self) -> &'static str {
+        "the conjugated verb"
+    }
+
+    fn check(&self, config: &Config, answer: &str) -> Option<bool> {
+        Some(self.answer(config, answer)? == Answer::Correct)
+    }
+
+    fn feedback(
+        &self,
+        data: &DataDir,
+        config: &Config,
+        ui: &mut dyn Ui,
+        answer: &str,
+        correct: bool,
+    ) {
+        if !correct {
+            ui.show(format!("Wrong! Correct answer is {}", self.solution()).into());
+            if self.answer(config, answer) == Some(Answer::WrongWordOrder) {
+                ui.show("The words are right, but the word order is not".into());
+            }
+        }
+        self.verb.play_conjugation(data, self.pronoun, self.tense);
+    }
+
+    fn replay(&self, data: &DataDir, slow: bool) -> bool {
+        if slow {
+            play_file_slowly_or_print_error(&self.verb.infinitive_audio_file_path(data));
+        } else {
+            self.verb.play_infinitive(data);
+        }
+        true
+    }
+
+    fn english(&self) -> Option<String> {
+        Some(self.verb.english.clone())
+    }
+
+    fn solution(&self) -> String {
+        self.verb
+            .pronoun_conjugation(self.pronoun, self.tense)
+            .unwrap_or_default()
+    }
+
+    /* The other persons of the same verb and tense, such as arbeitest for ich arbeite */
+    fn choices(&self) -> Vec<String> {
+        let Some(conjugation) = self.verb.conjugation(self.pronoun, self.tense) else {
+            return vec![];
+        };
+
+        let mut choices = vec![conjugation];
+        choices.extend(
+            self.tense
+                .pronouns()
+                .filter_map(|pronoun| self.verb.conjugation(pronoun, self.tense)),
+        );
+        choices
+    }
+}
+
+/// How close a typed conjugation is to the expected one
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Answer {
+    Correct,
+    WrongWordOrder,
+    Wrong,
+}
+
+impl Answer {
+    /// Compares the words of both, ignoring extra whitespace between them
+    pub(crate) fn check(expected: &str, input: &str) -> Self {
+        let expected: Vec<&str> = expected.split_whitespace().collect();
+        let input: Vec<&str> = input.split_whitespace().collect();
+        if expected == input {
+            return Answer::Correct;
+        }
+
+        let mut expected = expected;
+        let mut input = input;
+        expected.sort();
+        input.sort();
+        if expected == input {
+            Answer::WrongWordOrder
+        } else {
+            Answer::Wrong
+        }
+    }
+}
+
+/// The present tense of the verbs used to build the compound tenses
+struct Auxiliary([&'static str; 6]);
+
+static HABEN: Auxiliary = Auxiliary(["habe", "hast", "hat", "haben", "habt", "haben"]);
+static SEIN: Auxiliary = Auxiliary(["bin", "bist", "ist", "sind", "seid", "sind"]);
+static WERDEN: Auxiliary = Auxiliary(["werde", "wirst", "wird", "werden", "werdet", "werden"]);
+
+impl Auxiliary {
+    fn conjugate(&self, pronoun: &Pronoun) -> &'static str {
+        self.0[pronoun.column()]
+    }
+}
+
+impl Tense {
+    pub(crate) fn iter() -> Iter<'static, Tense> {
+        static TENSES: [Tense; 5] = [
+            Tense::Praesens,
+            Tense::Praeteritum,
+            Tense::Perfekt,
+            Tense::Futur,
+            Tense::Imperativ,
+        ];
+        TENSES.iter()
+    }
+
+    /// The pronouns for which this tense has a form
+    pub(crate) fn pronouns(&self) -> Box<dyn Iterator<Item = &'static Pronoun>> {
+        match self {
+            Tense::Imperativ => Box::new(Pronoun::iter().filter(|pronoun| {
+                matches!(pronoun, Pronoun::Du | Pronoun::Ihr | Pronoun::SieFormal)
+            })),
+            _ => Box::new(Pronoun::iter()),
+        }
+    }
+
+    /// One pronoun, picked at random, for each distinct form of this tense, in random order.
+    /// The pronouns that share a column in the CSV file (such as er, sie, es and man) have the
+    /// same form, so there is no point in asking each of them.
+    pub(crate) fn distinct_pronouns(&self) -> Vec<&'static Pronoun> {
+        let mut rng = thread_rng();
+
+        let mut classes: Vec<Vec<&'static Pronoun>> = Vec::new();
+        for pronoun in self.pronouns() {
+            match classes
+                .iter_mut()
+                .find(|class| class[0].column() == pronoun.column())
+            {
+                Some(class) => class.push(pronoun),
+                None => classes.push(vec![pronoun]),
+            }
+        }
+
+        classes.shuffle(&mut rng);
+        classes
+            .iter()
+            .map(|class| *class.choose(&mut rng).unwrap())
+            .collect()
+    }
+
+    /// The name used in the CLI and in the history
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Tense::Praesens => "praesens",
+            Tense::Praeteritum => "praeteritum",
+            Tense::Perfekt => "perfekt",
+            Tense::Futur => "futur",
+            Tense::Imperativ => "imperativ",
+        }
+    }
+}
+
+impl Tagged for Verb {
+    fn tag_column(&self) -> Option<&str> {
+        self.tags.as_deref()
+    }
+
+    fn level(&self) -> Option<Level> {
+        self.level
+    }
+}
+
+impl Display for Tense {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tense = match &self {
+            Tense::Praesens => "Präsens",
+            Tense::Praeteritum => "Präteritum",
+            Tense::Perfekt => "Perfekt",
+            Tense::Futur => "Futur I",
+            Tense::Imperativ => "Imperativ",
+        };
+        write!(f, "{}", tense)
+    }
+}
+
 impl Pronoun {
     pub fn iter() -> Iter<'static, Pronoun> {
         static PRONOUNS: [Pronoun; 11] = [
@@ -150,6 +554,35 @@ impl Pronoun {
         ];
         PRONOUNS.iter()
     }
+
+    /// The index of the conjugation column (ich, du, er, wir, ihr, sie) used by this pronoun
+    fn column(&self) -> usize {
+        match self {
+            Pronoun::Ich => 0,
+            Pronoun::Du => 1,
+            Pronoun::Er | Pronoun::Sie | Pronoun::Es | Pronoun::Man => 2,
+            Pronoun::Wir => 3,
+            Pronoun::Ihr => 4,
+            Pronoun::SieFormal | Pronoun::SiePluralFormal | Pronoun::SiePlural => 5,
+        }
+    }
+
+    /// A name that tells apart pronouns written the same, such as the formal and plural sie
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Pronoun::Ich => "ich",
+            Pronoun::Du => "du",
+            Pronoun::SieFormal => "sie_formal",
+            Pronoun::Er => "er",
+            Pronoun::Sie => "sie",
+            Pronoun::Es => "es",
+            Pronoun::Man => "man",
+            Pronoun::Wir => "wir",
+            Pronoun::Ihr => "ihr",
+            Pronoun::SiePluralFormal => "sie_plural_formal",
+            Pronoun::SiePlural => "sie_plural",
+        }
+    }
 }
 
 impl Display for Pronoun {
@@ -176,17 +609,81 @@ mod tests {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
-    use crate::types::verbs::Verb;
+    use crate::types::data::DataDir;
+    use crate::types::verbs::{Answer, Pronoun, Tense, Verb, VERBS_FILE};
 
     #[test]
     fn read_all() {
-        let verbs = Verb::read();
+        let verbs = Verb::read(&DataDir::new("")).unwrap();
 
         assert_eq!(verbs.len(), count_entries_in_csv_file());
     }
 
+    #[test]
+    fn read_present_tense_only_columns() {
+        let csv = "english,german,ich,du,er,wir,ihr,sie\n\
+                   to work,arbeiten,arbeite,arbeitest,arbeitet,arbeiten,arbeitet,arbeiten\n";
+        let verbs: Vec<Verb> = csv::Reader::from_reader(csv.as_bytes())
+            .deserialize()
+            .map(|r| r.unwrap())
+            .collect();
+
+        assert!(verbs[0].has_tense(&Tense::Praesens));
+        assert!(verbs[0].has_tense(&Tense::Futur));
+        assert!(!verbs[0].has_tense(&Tense::Praeteritum));
+    }
+
+    #[test]
+    fn compound_tenses() {
+        let verbs = Verb::read(&DataDir::new("")).unwrap();
+        let kommen = verbs.iter().find(|verb| verb.german == "kommen").unwrap();
+
+        assert_eq!(
+            kommen.pronoun_conjugation(&Pronoun::Wir, &Tense::Perfekt),
+            Some("wir sind gekommen".to_string())
+        );
+        assert_eq!(
+            kommen.pronoun_conjugation(&Pronoun::Du, &Tense::Futur),
+            Some("du wirst kommen".to_string())
+        );
+        assert_eq!(kommen.conjugation(&Pronoun::Ich, &Tense::Imperativ), None);
+    }
+
+    #[test]
+    fn separable_and_reflexive() {
+        let verbs = Verb::read(&DataDir::new("")).unwrap();
+        let anziehen = verbs.iter().find(|verb| verb.german == "anziehen").unwrap();
+
+        assert_eq!(anziehen.infinitive(), "sich anziehen");
+        assert_eq!(
+            anziehen.pronoun_conjugation(&Pronoun::Ich, &Tense::Praesens),
+            Some("ich ziehe mich an".to_string())
+        );
+        assert_eq!(
+            anziehen.pronoun_conjugation(&Pronoun::Wir, &Tense::Perfekt),
+            Some("wir haben uns angezogen".to_string())
+        );
+        assert_eq!(
+            anziehen.pronoun_conjugation(&Pronoun::Ihr, &Tense::Imperativ),
+            Some("zieht euch an!".to_string())
+        );
+    }
+
+    #[test]
+    fn distinct_pronouns() {
+        assert_eq!(Tense::Praesens.distinct_pronouns().len(), 6);
+        assert_eq!(Tense::Imperativ.distinct_pronouns().len(), 3);
+    }
+
+    #[test]
+    fn word_order() {
+        assert_eq!(Answer::check("rufe an", "rufe  an"), Answer::Correct);
+        assert_eq!(Answer::check("rufe an", "an rufe"), Answer::WrongWordOrder);
+        assert_eq!(Answer::check("wasche mich", "wasche"), Answer::Wrong);
+    }
+
     fn count_entries_in_csv_file() -> usize {
-        let file = File::open("verbs.csv").expect("Failed to open file");
+        let file = File::open(VERBS_FILE).expect("Failed to open file");
         BufReader::new(file).lines().count() - 1
     }
 }